@@ -43,34 +43,6 @@ fn main() -> ExitCode {
         },
     };
 
-    println!("Building jdk_selector_executor...");
-    let mut _cli_build_cmd = Command::new("cargo")
-        .args([
-            "build",
-            "--package",
-            "jdk_selector_executor",
-            "--bin",
-            "jdk_selector_executor",
-            "--release",
-        ])
-        .spawn();
-    let cli_build_cmd = match &mut _cli_build_cmd {
-        Ok(value) => value,
-        Err(e) => {
-            println!("{}", e);
-            println!("Couldn't build jdk_selector_executor successfully. (1)");
-            return ExitCode::FAILURE;
-        },
-    };
-
-    match cli_build_cmd.wait() {
-        Ok(..) => ExitCode::SUCCESS,
-        Err(e) => {
-            println!("{}", e);
-            println!("Couldn't build jdk_selector_executor successfully. (2)");
-            return ExitCode::FAILURE;
-        },
-    };
     println!("{}", "Built projects successfully!".green());
 
     println!("Collecting build outputs...");
@@ -83,9 +55,9 @@ fn main() -> ExitCode {
             return ExitCode::FAILURE;
         }
     };
-    let filenames: Vec<String> = vec![
+    // Executors are built into jdk_selector_cli, which acts as executor when invoked by other names
+    let filenames: Vec<String> = [
         "jdk_selector_cli",
-        "jdk_selector_executor"
     ].iter()
         .map(| value | {
             if cfg!(windows) {
@@ -130,7 +102,7 @@ fn main() -> ExitCode {
         },
     };
 
-    match populate_executors(&config, &filenames[1]) {
+    match populate_executors(&config, &filenames[0]) {
         Ok(..) => {},
         Err(e) => {
             println!("{}", e);
@@ -139,21 +111,11 @@ fn main() -> ExitCode {
         }
     };
 
-    match fs::remove_file(format!("./.build/{}", &filenames[1])) {
-        Ok(..) => {},
-        Err(e) => {
-            println!("{}", e);
-            println!("Couldn't remove original executor. (2)");
-            println!("But this step is optional so this warning will be ignored.");
-            println!("You should remove ${} yourself.", &filenames[1]);
-        },
-    };
-
     println!("{}", "Finished!".green());
     return ExitCode::SUCCESS;
 }
 
-fn check_files(dirs: ReadDir, filenames: &Vec<String>) -> Result<bool, std::io::Error> {
+fn check_files(dirs: ReadDir, filenames: &[String]) -> Result<bool, std::io::Error> {
     let mut cloned_names: Vec<String> = filenames.iter().map(|v| { v.to_string() }).collect();
 
     for dir in dirs {
@@ -161,7 +123,7 @@ fn check_files(dirs: ReadDir, filenames: &Vec<String>) -> Result<bool, std::io::
 
         cloned_names.retain(| value | filename.contains(value));
 
-        if cloned_names.len() == 0 {
+        if cloned_names.is_empty() {
             return Ok(true);
         };
     }
@@ -194,14 +156,15 @@ fn copy_files(filenames: &Vec<String>) -> Result<(), std::io::Error> {
 }
 
 // TODO: Determine if it is required to get rid of static strings here
-fn populate_executors(config: &Config, original_filename: &String) -> Result<(), std::io::Error> {
+fn populate_executors(config: &Config, original_filename: &str) -> Result<(), std::io::Error> {
     let extension = if env::consts::OS == "windows" { ".exe" } else { "" };
 
     for filename in config.executor.filenames.iter() {
-        match fs::copy(
-            format!("./.build/{}", original_filename),
-            format!("./.build/{}{}", filename, extension),
-        ) {
+        let link_path = format!("./.build/{}{}", filename, extension);
+        // Links left by previous builds would make creating new ones fail
+        let _ = fs::remove_file(&link_path);
+
+        match link_executor(original_filename, &link_path) {
             Ok(..) => {},
             Err(e) => {
                 return Err(e);
@@ -210,4 +173,16 @@ fn populate_executors(config: &Config, original_filename: &String) -> Result<(),
     }
 
     Ok(())
+}
+
+// Relative symbolic link keeps working wherever build outputs are copied to
+#[cfg(unix)]
+fn link_executor(original_filename: &str, link_path: &str) -> Result<(), std::io::Error> {
+    std::os::unix::fs::symlink(original_filename, link_path)
+}
+
+// Symbolic links require privilege on Windows, so hard links are used instead
+#[cfg(not(unix))]
+fn link_executor(original_filename: &str, link_path: &str) -> Result<(), std::io::Error> {
+    fs::hard_link(format!("./.build/{}", original_filename), link_path)
 }
\ No newline at end of file
@@ -0,0 +1,142 @@
+use std::{env, fs, process};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use jdk_selector_shared::models::config::Config;
+use jdk_selector_shared::models::jdk_info::JdkInfo;
+use jdk_selector_shared::utils::config_loader::{config_path, set_config};
+use jdk_selector_shared::utils::resolution_cache::cache_path;
+use jdk_selector_shared::utils::resolver::JDK_OVERRIDE_ENV;
+
+const ITERATIONS: u32 = 200;
+const JDK_COUNT: usize = 40;
+
+// Measures whole run of java -version through executor, including process startup which in-process
+// benchmark of jdk_selector_executor leaves out. Stub java exits right away so executor dominates
+fn main() {
+    if !cfg!(unix) {
+        println!("Executor is found through name of symbolic link, which this benchmark relies on Unix for.");
+        return;
+    }
+
+    let dir = env::temp_dir().join(format!("jdk-selector-bench-shim-{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+
+    // Config is looked up under HOME, so both this process and executors use the one created here
+    env::set_var("HOME", dir.join("home"));
+    env::remove_var("XDG_CONFIG_HOME");
+    env::remove_var(JDK_OVERRIDE_ENV);
+
+    let config_path = match config_path() {
+        Ok(value) => value.config_dir().join("config.json"),
+        Err(e) => {
+            println!("{}", e);
+            process::exit(1);
+        },
+    };
+
+    let stub_jdk_path = dir.join("stub-jdk");
+    let shim_path = match create_stub_jdk(&stub_jdk_path).and_then(| _ | link_shim(&dir.join("bin"))) {
+        Ok(value) => value,
+        Err(e) => {
+            println!("{}", e);
+            process::exit(1);
+        },
+    };
+
+    let mut sample = Config::default();
+    for index in 0..JDK_COUNT {
+        let mut jdk_info = JdkInfo::new(&format!("temurin-{}", index), &format!("/opt/jdk-{}", index));
+        jdk_info.version = Some(format!("{}.0.{}+7", 8 + index % 14, index));
+        jdk_info.vendor = Some("Eclipse Adoptium".to_string());
+        sample.jdk_info_list.push(jdk_info);
+    }
+    sample.jdk_info_list.push(JdkInfo::new("stub", &stub_jdk_path.to_string_lossy()));
+    sample.selected_jdk = Some("stub".to_string());
+
+    if let Err(e) = set_config(&config_path, &sample) {
+        println!("{}", e);
+        process::exit(1);
+    }
+
+    // Project lookup walks up from current directory, so it has to be outside of any project
+    let current_dir = env::temp_dir();
+    let without_cache = measure(&shim_path, &current_dir, || {
+        // Executor writes the cache again whenever it's missing
+        let _ = fs::remove_file(cache_path(&config_path));
+    });
+    let with_cache = measure(&shim_path, &current_dir, || {});
+
+    println!("java -version through executor with {} JDKs, average of {} runs", JDK_COUNT + 1, ITERATIONS);
+    println!("\t config.json: {:?}", without_cache);
+    println!("\t cache:       {:?}", with_cache);
+    println!("\t speedup:     {:.1}x", without_cache.as_secs_f64() / with_cache.as_secs_f64());
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[cfg(unix)]
+fn create_stub_jdk(jdk_path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let java_path = jdk_path.join("bin").join("java");
+    fs::create_dir_all(jdk_path.join("bin"))?;
+    fs::write(&java_path, "#!/bin/sh\nexit 0\n")?;
+
+    return fs::set_permissions(&java_path, fs::Permissions::from_mode(0o755));
+}
+
+#[cfg(unix)]
+fn link_shim(bin_path: &Path) -> std::io::Result<PathBuf> {
+    let shim_path = bin_path.join("java");
+    fs::create_dir_all(bin_path)?;
+    std::os::unix::fs::symlink(env!("CARGO_BIN_EXE_jdk_selector_cli"), &shim_path)?;
+
+    return Ok(shim_path);
+}
+
+#[cfg(not(unix))]
+fn create_stub_jdk(_jdk_path: &Path) -> std::io::Result<()> {
+    unreachable!()
+}
+
+#[cfg(not(unix))]
+fn link_shim(_bin_path: &Path) -> std::io::Result<PathBuf> {
+    unreachable!()
+}
+
+// prepare runs before every spawn and isn't counted
+fn measure<F: Fn()>(shim_path: &Path, current_dir: &Path, prepare: F) -> Duration {
+    let run = || {
+        prepare();
+
+        let start = Instant::now();
+        let status = Command::new(shim_path)
+            .arg("-version")
+            .current_dir(current_dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        let elapsed = start.elapsed();
+
+        match status {
+            Ok(value) if value.success() => elapsed,
+            Ok(value) => {
+                println!("Executor failed with {}", value);
+                process::exit(1);
+            },
+            Err(e) => {
+                println!("{}", e);
+                process::exit(1);
+            },
+        }
+    };
+
+    // Warm up file system cache first
+    for _ in 0..ITERATIONS / 10 {
+        run();
+    }
+
+    return (0..ITERATIONS).map(| _ | run()).sum::<Duration>() / ITERATIONS;
+}
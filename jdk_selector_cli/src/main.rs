@@ -1,24 +1,94 @@
-use std::{env};
-use std::path::Path;
+mod utils;
+
+use std::{env, fs, io};
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 use colored::Colorize;
 use jdk_selector_shared::models::config::Config;
 use jdk_selector_shared::models::jdk_info::JdkInfo;
-use jdk_selector_shared::print_on_debug;
-use jdk_selector_shared::utils::config_loader::{config, config_path, file_path, set_config};
+use jdk_selector_shared::{print_on_debug, trace};
+use jdk_selector_shared::utils::config_loader::{
+    config,
+    config_path,
+    file_path,
+    invoked_file_name,
+    lock_config,
+    quarantined_config_paths,
+    set_config,
+    JsonErrorOrIOOrInvalidPathError,
+};
+use jdk_selector_shared::utils::config_recovery::recover_config;
+use jdk_selector_shared::utils::jdk_metadata::refresh_metadata;
+use jdk_selector_shared::utils::jdk_scanner::{default_scan_roots, scan_jdk_homes};
+use jdk_selector_shared::utils::jdk_validator::{validate_jdk_home, JdkKind};
+use jdk_selector_shared::utils::shim::{rehash, tool_file_names};
+use jdk_selector_shared::utils::trace::{enable_trace, TRACE_ENV};
+use jdk_selector_shared::utils::vendor::vendor_alias;
+use jdk_selector_shared::utils::version_constraint::{find_jdk_info, VersionConstraint};
+use jdk_selector_executor::{run_command, run_executor};
+use crate::utils::command::jdk_command;
+use crate::utils::matrix::{format_duration, run_matrix, write_json_report, write_junit_report};
+use crate::utils::shell::Shell;
+use crate::utils::shell_init::init_script;
+use jdk_selector_shared::utils::resolver::{
+    env_override,
+    layers,
+    resolve,
+    resolve_tool_binary,
+    ResolveError,
+    JDK_OVERRIDE_ENV,
+};
+use jdk_selector_shared::utils::project_file::{
+    find_project_file,
+    read_project_file,
+    write_project_file,
+    PROJECT_FILE_NAME,
+};
+
+const MATRIX_LOG_DIR: &str = "jdk-matrix-logs";
+
+// Every executor is a link to this binary, so it acts as CLI only when invoked by its own name
+const CLI_NAME: &str = "jdk_selector_cli";
 
 fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if let Some(name) = invoked_file_name() {
+        if name.strip_suffix(env::consts::EXE_SUFFIX).unwrap_or(&name) != CLI_NAME {
+            return run_executor(&name, args);
+        }
+    }
+
     #[cfg(windows)]
     colored::control::set_virtual_terminal(true).ok(); // Quark for Windows to enable colored
 
-    let args: Vec<String> = env::args().skip(1).collect();
+    // --trace can be anywhere before -- and applies to executors started by this process as well.
+    // Anything after -- is a command given to exec or matrix and kept as is
+    let command_start = args.iter().position(| value | value == "--").unwrap_or(args.len());
+    let trace_requested = args[..command_start].iter().any(| value | value == "--trace");
+    let args: Vec<String> = args.iter()
+        .enumerate()
+        .filter(| (index, value) | *index >= command_start || *value != "--trace")
+        .map(| (_, value) | value.to_string())
+        .collect();
+    if trace_requested {
+        enable_trace();
+        env::set_var(TRACE_ENV, "1");
+    }
+
     print_on_debug!(args.join(" "));
 
+    // Output of init and shell is evaluated by shell, so errors must not end up in it
+    let prints_script = matches!(args.first().map(| v | v.as_str()), Some("init") | Some("shell"));
+
     let _config_path = config_path();
     let config_path = match &_config_path {
         Ok(value) => value.config_dir(),
         Err(e) => {
-            println!("{}", e);
+            print_error(prints_script, e);
             return ExitCode::FAILURE;
         },
     };
@@ -26,17 +96,43 @@ fn main() -> ExitCode {
 
     let appended_config_path = config_path.join("config.json");
     print_on_debug!(appended_config_path.display());
+    trace!("Config file: {}", appended_config_path.display());
+
+    // Released when main returns so commands changing config run their load, modify and save cycle exclusively
+    let _lock = if changes_config(&args) {
+        match lock_config(&appended_config_path) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                print_error(prints_script, e);
+                print_error(prints_script, "Couldn't lock config file.");
+                return ExitCode::FAILURE;
+            },
+        }
+    } else {
+        None
+    };
+
+    // Config subcommands have to work even if config couldn't be loaded
+    if args.first().map(| v | v.as_str()) == Some("config") {
+        return match args.get(1).map(| v | v.as_str()) {
+            Some("repair") => repair_config(&appended_config_path, args.get(2)),
+            _ => {
+                show_config_help();
+                ExitCode::FAILURE
+            },
+        };
+    }
 
     let config = match config(&appended_config_path) {
         Ok(value) => value,
         Err(e) => {
-            println!("{}", e);
+            print_error(prints_script, e);
             return ExitCode::FAILURE;
         },
     };
     print_on_debug!(&config);
 
-    return match args.get(0).map(| v | v.as_str()) {
+    return match args.first().map(| v | v.as_str()) {
         Some("help") => {
             let _help_type = args.get(1);
             let help_type = match &_help_type {
@@ -50,12 +146,14 @@ fn main() -> ExitCode {
             return show_detailed_help(help_type);
         },
         Some("add") => {
+            let force = args.iter().any(| value | value == "--force");
             let args: Vec<String> = args.iter()
                 .skip(1)
+                .filter(| value | *value != "--force")
                 .take(2)
                 .map(| value | value.to_string())
                 .collect();
-            let _name = args.get(0);
+            let _name = args.first();
             let name = match _name {
                 Some(value) => value,
                 None => {
@@ -72,13 +170,13 @@ fn main() -> ExitCode {
                 },
             };
 
-            return add_jdk_info(&config, &appended_config_path, name, path);
+            return add_jdk_info(&config, &appended_config_path, name, path, force);
         },
         Some("remove") => {
             let _name = args.iter()
                 .skip(1)
                 .take(1)
-                .nth(0);
+                .next();
             let name = match _name {
                 Some(value) => value,
                 None => {
@@ -93,7 +191,7 @@ fn main() -> ExitCode {
             let _name = args.iter()
                 .skip(1)
                 .take(1)
-                .nth(0);
+                .next();
             let name = match _name {
                 Some(value) => value,
                 None => {
@@ -107,15 +205,100 @@ fn main() -> ExitCode {
         Some("list") => {
             return show_jdk_list(&config);
         },
+        Some("refresh") => {
+            return refresh_jdk_info(&config, &appended_config_path, args.get(1));
+        },
+        Some("scan") => {
+            return scan_jdk_info(&config, &appended_config_path, &args[1..]);
+        },
+        Some("local") => {
+            return local_jdk(&config, args.get(1));
+        },
+        Some("shell") => {
+            return shell_jdk(&config, &args[1..]);
+        },
+        Some("prefer") => {
+            return prefer_vendors(&config, &appended_config_path, &args[1..]);
+        },
+        Some("setting") => {
+            return change_setting(&config, &appended_config_path, &args[1..]);
+        },
+        Some("which") => {
+            return which_tool(&config, &args[1..]);
+        },
+        Some("exec") => {
+            return exec_command(&config, &args[1..]);
+        },
+        Some("matrix") => {
+            return run_command_matrix(&config, &args[1..]);
+        },
+        Some("init") => {
+            return init_shell(&config, &args[1..]);
+        },
+        Some("rehash") => {
+            return rehash_executors(&config);
+        },
+        Some("env") => {
+            return jdk_env(&config, &appended_config_path, &args[1..]);
+        },
+        Some("options") => {
+            return jdk_options(&config, &appended_config_path, &args[1..]);
+        },
+        Some("tool") => {
+            return match args.get(1).map(| v | v.as_str()) {
+                Some("set") => match (args.get(2), args.get(3)) {
+                    (Some(tool), Some(selector)) => set_tool_override(&config, &appended_config_path, tool, selector),
+                    _ => {
+                        show_tool_help();
+                        ExitCode::FAILURE
+                    },
+                },
+                Some("unset") => match args.get(2) {
+                    Some(tool) => unset_tool_override(&config, &appended_config_path, tool),
+                    None => {
+                        show_tool_help();
+                        ExitCode::FAILURE
+                    },
+                },
+                Some("list") => show_tool_overrides(&config),
+                _ => {
+                    show_tool_help();
+                    ExitCode::FAILURE
+                },
+            };
+        },
         Some(..) => show_main_help(),
         None => show_main_help(),
     };
 }
 
+// Read-only commands don't wait for the lock, and neither does scan which takes it only after user has answered
+fn changes_config(args: &[String]) -> bool {
+    let arg = | index: usize | args.get(index).map(| v | v.as_str());
+
+    return match arg(0) {
+        Some("add" | "remove" | "use" | "refresh") => true,
+        Some("prefer") => arg(1).is_some(),
+        Some("setting") => arg(2).is_some(),
+        Some("env" | "options" | "tool") => arg(1) != Some("list"),
+        Some("scan") => arg(1) == Some("root") && matches!(arg(2), Some("add" | "remove")),
+        Some("config") => arg(1) == Some("repair"),
+        _ => false,
+    };
+}
+
+fn print_error(to_stderr: bool, message: impl Display) {
+    if to_stderr {
+        eprintln!("{}", message);
+    } else {
+        println!("{}", message);
+    }
+}
+
 fn file_name() -> Result<String, ()> {
     let file_path = file_path();
     let _file_name_with_extension = match &file_path {
-        Ok(value) => value.iter().last(),
+        Ok(value) => value.iter().next_back(),
         Err(..) => {
             return Err(());
         },
@@ -125,7 +308,7 @@ fn file_name() -> Result<String, ()> {
         None => return Err(()),
     };
 
-    let file_name = match file_name_with_extension.split(".").nth(0) {
+    let file_name = match file_name_with_extension.split(".").next() {
         Some(value) => value,
         None => return Err(()),
     };
@@ -146,13 +329,32 @@ fn show_main_help() -> ExitCode {
     println!("JDK Selector");
     println!();
     println!("USAGE:");
-    println!("\t {} [SUBCOMMAND]", &file_name);
+    println!("\t {} [--trace] [SUBCOMMAND]", &file_name);
+    println!();
+    println!("OPTIONS:");
+    println!("\t --trace Print how config and JDKs are resolved to stderr");
+    println!("\t         Set {}=1 to make executors do the same", TRACE_ENV);
     println!();
     println!("SUBCOMMANDS:");
-    println!("\t add    Add new JDK information");
-    println!("\t remove Remove specific JDK information");
-    println!("\t use    Set specific JDK information to use for executors from JDK Selector");
-    println!("\t list   Show list of JDK information");
+    println!("\t add     Add new JDK information");
+    println!("\t remove  Remove specific JDK information");
+    println!("\t use     Set specific JDK information to use for executors from JDK Selector");
+    println!("\t list    Show list of JDK information");
+    println!("\t refresh Read metadata of JDK information again from JDK");
+    println!("\t scan    Find installed JDKs and add them as JDK information");
+    println!("\t local   Set specific JDK information to use in current directory and below");
+    println!("\t shell   Print command to set specific JDK information to use in current shell");
+    println!("\t prefer  Set vendors preferred when version constraint matches several JDKs");
+    println!("\t setting Show or change behavior of executors");
+    println!("\t tool    Set specific JDK information to use for specific tool regardless of others");
+    println!("\t env     Set environment variables for tools of specific JDK information");
+    println!("\t options Set JVM options for tools of specific JDK information");
+    println!("\t which   Show which binary executor of specific tool runs in current directory");
+    println!("\t exec    Run command with specific JDK information without changing selection");
+    println!("\t matrix  Run command with every JDK information matching constraint and summarize results");
+    println!("\t init    Print script integrating JDK Selector into bash, zsh or fish");
+    println!("\t rehash  Create executors for every tool of added JDKs and remove unused ones");
+    println!("\t config  Manage config file itself");
     println!();
     println!("See \'{} help <subcommand>\' for more information on a specific command.", &file_name);
     return ExitCode::SUCCESS;
@@ -165,6 +367,21 @@ fn show_detailed_help(help_type: &str) -> ExitCode {
         "remove" => show_remove_help(),
         "use" => show_use_help(),
         "list" => show_list_help(),
+        "refresh" => show_refresh_help(),
+        "scan" => show_scan_help(),
+        "local" => show_local_help(),
+        "shell" => show_shell_help(),
+        "prefer" => show_prefer_help(),
+        "setting" => show_setting_help(),
+        "tool" => show_tool_help(),
+        "env" => show_env_help(),
+        "options" => show_options_help(),
+        "which" => show_which_help(),
+        "exec" => show_exec_help(),
+        "matrix" => show_matrix_help(),
+        "init" => show_init_help(),
+        "rehash" => show_rehash_help(),
+        "config" => show_config_help(),
         _ => show_main_help(),
     }
 }
@@ -180,7 +397,7 @@ fn show_add_help() -> ExitCode {
     };
 
     println!("USAGE:");
-    println!("\t {} add name path", &file_name);
+    println!("\t {} add [--force] name path", &file_name);
     println!();
     println!("DESCRIPTION:");
     println!("\t This command will add JDK information into config for the future use.");
@@ -191,6 +408,9 @@ fn show_add_help() -> ExitCode {
     println!();
     println!("\t Path must be JDK path as same as JAVA_HOME which doesn't specify bin folder.");
     println!("\t It's highly recommended to not contain any UTF-8 character in path.");
+    println!();
+    println!("\t Path is checked to contain bin/java before adding. Paths to bin folder, java binary");
+    println!("\t or macOS JDK bundle are corrected to JDK home. --force will skip these checks.");
     return ExitCode::SUCCESS;
 }
 
@@ -210,6 +430,9 @@ fn show_remove_help() -> ExitCode {
     println!("DESCRIPTION:");
     println!("\t This command will find specific JDK information by name from config and remove it");
     println!("\t from config if exists.");
+    println!();
+    println!("\t If the JDK is currently in-use, the first remaining JDK will be used instead.");
+    println!("\t Tool overrides left without any JDK to use are removed along with it.");
     return ExitCode::SUCCESS;
 }
 
@@ -224,11 +447,21 @@ fn show_use_help() -> ExitCode {
     };
 
     println!("USAGE:");
-    println!("\t {} use name", &file_name);
+    println!("\t {} use <name | constraint>", &file_name);
     println!();
     println!("DESCRIPTION:");
     println!("\t This command will find specific JDK information by name from config and mark it to");
     println!("\t let executors use it in the future.");
+    println!();
+    println!("\t If no JDK has given name, it's read as version constraint matched against version and");
+    println!("\t vendor metadata. Highest matching version is used, then vendor set by prefer command.");
+    println!();
+    println!("\t i.e {} use 17", &file_name);
+    println!("\t     {} use \">=11,<17\"", &file_name);
+    println!("\t     {} use temurin@21", &file_name);
+    println!();
+    println!("\t Legacy versions like 1.8.0_321 are matched as 8. Constraints are also accepted by local");
+    println!("\t and shell commands and {}, which resolve them whenever executors run.", JDK_OVERRIDE_ENV);
     return ExitCode::SUCCESS;
 }
 
@@ -247,148 +480,1667 @@ fn show_list_help() -> ExitCode {
     println!();
     println!("DESCRIPTION:");
     println!("\t This command will show all JDK information.");
+    println!("\t Version, vendor, architecture and implementation are shown as well if known.");
     return ExitCode::SUCCESS;
 }
 
-fn add_jdk_info(config: &Config, config_path: &Path, name: &String, path: &String) -> ExitCode {
-    let existed = config.jdk_info_list.iter()
-        .filter(| value | value.name == name.to_string())
-        .nth(0);
+fn show_refresh_help() -> ExitCode {
+    let file_name = match file_name() {
+        Ok(value) => value,
+        Err(..) => {
+            println!("Couldn't retrieve current executable filename.");
+            println!("Assuming it's using default name...");
+            "jdk_selector_cli".to_string()
+        },
+    };
 
-    match existed {
-        Some(..) => {
-            println!("Name must be unique.");
-            return ExitCode::FAILURE;
-        }
-        None => {}
-    }
+    println!("USAGE:");
+    println!("\t {} refresh [name]", &file_name);
+    println!();
+    println!("DESCRIPTION:");
+    println!("\t This command will read version, vendor, architecture and implementation again");
+    println!("\t from release file of every JDK, or JDK named name if given, and save them into config.");
+    println!();
+    println!("\t JDKs without release file are asked by running their java. Its result is reused");
+    println!("\t until java binary is modified.");
+    println!();
+    println!("\t Run this after updating JDK in place.");
+    return ExitCode::SUCCESS;
+}
 
-    let mut updated_list: Vec<JdkInfo> = config.jdk_info_list.iter()
-        .map(| value | JdkInfo {
-            name: value.name.to_string(),
-            path: value.path.to_string()
-        })
-        .collect();
-    updated_list.push(JdkInfo {
-        name: name.to_string(),
-        path: path.to_string()
-    });
+fn show_scan_help() -> ExitCode {
+    let file_name = match file_name() {
+        Ok(value) => value,
+        Err(..) => {
+            println!("Couldn't retrieve current executable filename.");
+            println!("Assuming it's using default name...");
+            "jdk_selector_cli".to_string()
+        },
+    };
 
-    let updated_config = Config {
-        selected_jdk: config.selected_jdk.or(Some(updated_list.len() - 1)),
-        jdk_info_list: updated_list,
+    println!("USAGE:");
+    println!("\t {} scan [--yes] [--root path]...", &file_name);
+    println!("\t {} scan root [list | add path | remove path]", &file_name);
+    println!();
+    println!("DESCRIPTION:");
+    println!("\t This command will search well-known locations and roots added by scan root add");
+    println!("\t for JDKs having both bin/java and release file. JDKs already in config are skipped.");
+    println!();
+    println!("\t Found JDKs are named after their vendor and version and added after confirmation.");
+    println!("\t --yes will add them without asking and --root will search path once as well.");
+    println!();
+    println!("\t scan root manages additional roots saved in config.");
+    return ExitCode::SUCCESS;
+}
+
+fn show_local_help() -> ExitCode {
+    let file_name = match file_name() {
+        Ok(value) => value,
+        Err(..) => {
+            println!("Couldn't retrieve current executable filename.");
+            println!("Assuming it's using default name...");
+            "jdk_selector_cli".to_string()
+        },
     };
 
-    return match set_config(config_path, &updated_config) {
-        Ok(..) => {
-            println!("{} is successfully added.", name);
-            ExitCode::SUCCESS
-        }
-        Err(e) => {
-            println!("{}", e);
-            println!("Couldn't add new JDK information.");
-            ExitCode::FAILURE
-        }
-    }
+    println!("USAGE:");
+    println!("\t {} local [name | constraint | --unset]", &file_name);
+    println!();
+    println!("DESCRIPTION:");
+    println!("\t This command will write name or version constraint into {} in current directory.", PROJECT_FILE_NAME);
+    println!("\t Executors look for the nearest {} from their working directory up to root", PROJECT_FILE_NAME);
+    println!("\t and prefer it over JDK set by use command.");
+    println!();
+    println!("\t Without name, it will show which JDK is set for current directory and by which file.");
+    println!("\t --unset will remove {} from current directory.", PROJECT_FILE_NAME);
+    return ExitCode::SUCCESS;
 }
 
-fn remove_jdk_info(config: &Config, config_path: &Path, name: &String) -> ExitCode {
-    let existed = config.jdk_info_list.iter()
-        .filter(| value | value.name == name.to_string())
-        .nth(0);
+fn show_shell_help() -> ExitCode {
+    let file_name = match file_name() {
+        Ok(value) => value,
+        Err(..) => {
+            println!("Couldn't retrieve current executable filename.");
+            println!("Assuming it's using default name...");
+            "jdk_selector_cli".to_string()
+        },
+    };
 
-    match existed {
-        Some(..) => {}
-        None => {
-            println!("Couldn't find JDK Information named {}.", name);
-            return ExitCode::FAILURE;
-        }
-    }
+    println!("USAGE:");
+    println!("\t {} shell [--shell bash|zsh|fish|powershell|cmd] [name | --unset]", &file_name);
+    println!();
+    println!("DESCRIPTION:");
+    println!("\t This command will print command setting {} for current shell.", JDK_OVERRIDE_ENV);
+    println!("\t Executors prefer {} over project files and JDK set by use command.", JDK_OVERRIDE_ENV);
+    println!();
+    println!("\t i.e eval \"$({} shell \"JDK 1.8\")\"", &file_name);
+    println!();
+    println!("\t Without name, it will show JDK set for current shell.");
+    println!("\t --unset will print command removing {} instead.", JDK_OVERRIDE_ENV);
+    println!("\t Shell is detected from SHELL environment variable unless --shell is given.");
+    return ExitCode::SUCCESS;
+}
 
-    let updated_config = Config {
-        selected_jdk: config.selected_jdk,
-        jdk_info_list: config.jdk_info_list.iter()
-            .filter(| value | value.name != name.to_string())
-            .map(| value | JdkInfo {
-                name: value.name.to_string(),
-                path: value.path.to_string()
-            })
-            .collect(),
+fn show_prefer_help() -> ExitCode {
+    let file_name = match file_name() {
+        Ok(value) => value,
+        Err(..) => {
+            println!("Couldn't retrieve current executable filename.");
+            println!("Assuming it's using default name...");
+            "jdk_selector_cli".to_string()
+        },
     };
 
-    return match set_config(config_path, &updated_config) {
-        Ok(..) => {
-            println!("{} is successfully removed.", name);
-            ExitCode::SUCCESS
-        }
-        Err(e) => {
-            println!("{}", e);
-            println!("Couldn't remove the JDK information.");
-            ExitCode::FAILURE
-        }
-    }
+    println!("USAGE:");
+    println!("\t {} prefer [vendor... | --clear]", &file_name);
+    println!();
+    println!("DESCRIPTION:");
+    println!("\t This command will set vendors preferred, most preferred first, when several JDKs match");
+    println!("\t version constraint with same version. Vendors are short names like temurin or zulu.");
+    println!();
+    println!("\t Without vendor, it will show preferred vendors. --clear will remove them.");
+    return ExitCode::SUCCESS;
 }
 
-fn use_jdk(config: &Config, config_path: &Path, name: &String) -> ExitCode {
-    let index = config.jdk_info_list.iter()
-        .position(| value | value.name == name.to_string());
+fn show_setting_help() -> ExitCode {
+    let file_name = match file_name() {
+        Ok(value) => value,
+        Err(..) => {
+            println!("Couldn't retrieve current executable filename.");
+            println!("Assuming it's using default name...");
+            "jdk_selector_cli".to_string()
+        },
+    };
 
-    match index {
-        Some(..) => {}
-        None => {
-            println!("Couldn't find JDK Information named {}.", name);
-            return ExitCode::FAILURE;
-        }
-    }
+    println!("USAGE:");
+    println!("\t {} setting [key [true | false]]", &file_name);
+    println!();
+    println!("DESCRIPTION:");
+    println!("\t This command will change setting of key, or show its value if value isn't given.");
+    println!("\t Without key, it will show all settings.");
+    println!();
+    println!("KEYS:");
+    println!("\t tool_fallback     Run tool from the newest JDK providing it if selected JDK doesn't have it");
+    println!("\t export_java_home  Set JAVA_HOME to selected JDK and put its bin first in PATH for tools");
+    println!("\t                   and processes they start. Enabled by default");
+    return ExitCode::SUCCESS;
+}
 
-    let updated_config = Config {
-        selected_jdk: index,
-        jdk_info_list: config.jdk_info_list.iter()
-            .map(| value | JdkInfo {
-                name: value.name.to_string(),
-                path: value.path.to_string()
-            })
-            .collect(),
+fn show_tool_help() -> ExitCode {
+    let file_name = match file_name() {
+        Ok(value) => value,
+        Err(..) => {
+            println!("Couldn't retrieve current executable filename.");
+            println!("Assuming it's using default name...");
+            "jdk_selector_cli".to_string()
+        },
     };
 
-    return match set_config(config_path, &updated_config) {
-        Ok(..) => {
-            println!("{} is now current JDK.", name);
-            ExitCode::SUCCESS
-        }
-        Err(e) => {
-            println!("{}", e);
-            println!("Couldn't set JDK.");
-            ExitCode::FAILURE
-        }
-    }
+    println!("USAGE:");
+    println!("\t {} tool set <tool> <name | constraint>", &file_name);
+    println!("\t {} tool unset <tool>", &file_name);
+    println!("\t {} tool list", &file_name);
+    println!();
+    println!("DESCRIPTION:");
+    println!("\t set will make executor of tool, like jshell, always use given JDK regardless of project");
+    println!("\t files and JDK set by use command. Only {} takes priority over it.", JDK_OVERRIDE_ENV);
+    println!();
+    println!("\t i.e {} tool set jshell 21", &file_name);
+    println!();
+    println!("\t unset will remove override of tool. list will show all overrides.");
+    return ExitCode::SUCCESS;
 }
 
-fn show_jdk_list(config: &Config) -> ExitCode {
-    let selected = match config.selected_jdk {
-        Some(..) => true,
-        None => false,
+fn show_env_help() -> ExitCode {
+    let file_name = match file_name() {
+        Ok(value) => value,
+        Err(..) => {
+            println!("Couldn't retrieve current executable filename.");
+            println!("Assuming it's using default name...");
+            "jdk_selector_cli".to_string()
+        },
     };
 
-    println!("Recognized JDK list");
+    println!("USAGE:");
+    println!("\t {} env set <name> <key> <value>", &file_name);
+    println!("\t {} env unset <name> <key>", &file_name);
+    println!("\t {} env list <name>", &file_name);
+    println!();
+    println!("DESCRIPTION:");
+    println!("\t set will make executors set environment variable whenever they run tools of given JDK.");
+    println!("\t It replaces value inherited from shell, so flags like MAVEN_OPTS can be kept per JDK.");
     println!();
+    println!("\t unset will remove environment variable from JDK. list will show all of them.");
+    return ExitCode::SUCCESS;
+}
 
-    for (index, item) in config.jdk_info_list.iter().enumerate() {
-        if selected && index == config.selected_jdk.unwrap() {
-            println!(
-                "{} is located on \"{}\" {}",
-                &item.name,
-                &item.path,
-                "(currently in-use)".green(),
-            );
-        } else {
-            println!(
-                "{} is located on \"{}\"",
-                &item.name,
-                &item.path,
-            );
-        }
-    }
+fn show_options_help() -> ExitCode {
+    let file_name = match file_name() {
+        Ok(value) => value,
+        Err(..) => {
+            println!("Couldn't retrieve current executable filename.");
+            println!("Assuming it's using default name...");
+            "jdk_selector_cli".to_string()
+        },
+    };
 
+    println!("USAGE:");
+    println!("\t {} options set <name> <option>...", &file_name);
+    println!("\t {} options clear <name>", &file_name);
+    println!("\t {} options list <name>", &file_name);
+    println!();
+    println!("DESCRIPTION:");
+    println!("\t set will replace JVM options passed whenever executors run tools of given JDK.");
+    println!("\t They come before any given argument, so arguments can still override them.");
+    println!("\t Tools other than java and javaw receive them with -J prefix.");
+    println!();
+    println!("\t i.e {} options set \"JDK 1.8\" -XX:MaxPermSize=256m", &file_name);
+    println!();
+    println!("\t clear will remove all options from JDK. list will show them.");
     return ExitCode::SUCCESS;
-}
\ No newline at end of file
+}
+
+fn show_which_help() -> ExitCode {
+    let file_name = match file_name() {
+        Ok(value) => value,
+        Err(..) => {
+            println!("Couldn't retrieve current executable filename.");
+            println!("Assuming it's using default name...");
+            "jdk_selector_cli".to_string()
+        },
+    };
+
+    println!("USAGE:");
+    println!("\t {} which [--explain] <tool>", &file_name);
+    println!();
+    println!("DESCRIPTION:");
+    println!("\t This command will resolve tool, like java or javac, exactly as its executor would in");
+    println!("\t current directory and environment, and print path of the binary it would run.");
+    println!();
+    println!("\t --explain will list every place JDK can be selected from, in order of priority,");
+    println!("\t and whether it applied.");
+    return ExitCode::SUCCESS;
+}
+
+fn show_exec_help() -> ExitCode {
+    let file_name = match file_name() {
+        Ok(value) => value,
+        Err(..) => {
+            println!("Couldn't retrieve current executable filename.");
+            println!("Assuming it's using default name...");
+            "jdk_selector_cli".to_string()
+        },
+    };
+
+    println!("USAGE:");
+    println!("\t {} exec <name|constraint> -- <command> [arguments...]", &file_name);
+    println!();
+    println!("DESCRIPTION:");
+    println!("\t This command will run given command, like mvn test or ./gradlew build, with JAVA_HOME,");
+    println!("\t PATH and {} pointing at specific JDK information.", JDK_OVERRIDE_ENV);
+    println!("\t Environment variables set for the JDK information are applied as well.");
+    println!("\t Config isn't changed, so several commands can run with different JDKs at the same time.");
+    println!();
+    println!("EXAMPLES:");
+    println!("\t {} exec 8 -- mvn -pl legacy test", &file_name);
+    println!("\t {} exec temurin@17 -- ./gradlew build", &file_name);
+    return ExitCode::SUCCESS;
+}
+
+fn show_matrix_help() -> ExitCode {
+    let file_name = match file_name() {
+        Ok(value) => value,
+        Err(..) => {
+            println!("Couldn't retrieve current executable filename.");
+            println!("Assuming it's using default name...");
+            "jdk_selector_cli".to_string()
+        },
+    };
+
+    println!("USAGE:");
+    println!("\t {} matrix [OPTIONS] -- <command> [arguments...]", &file_name);
+    println!();
+    println!("DESCRIPTION:");
+    println!("\t This command will run given command once per JDK information, the same way as exec does,");
+    println!("\t and print whether it passed and how long it took with each of them.");
+    println!("\t Output of each run is written to <name>.log in log directory instead of terminal.");
+    println!();
+    println!("OPTIONS:");
+    println!("\t --filter <constraint> Run only with JDK information matching constraint, like >=11 or temurin@17");
+    println!("\t --jobs <count>        Run up to count of them at the same time (default: 1)");
+    println!("\t --logs <directory>    Write logs to directory (default: {})", MATRIX_LOG_DIR);
+    println!("\t --junit <path>        Write JUnit XML report to path");
+    println!("\t --json <path>         Write JSON report to path");
+    println!();
+    println!("EXAMPLES:");
+    println!("\t {} matrix --filter \">=11\" --jobs 2 -- ./gradlew test", &file_name);
+    return ExitCode::SUCCESS;
+}
+
+fn show_init_help() -> ExitCode {
+    let file_name = match file_name() {
+        Ok(value) => value,
+        Err(..) => {
+            println!("Couldn't retrieve current executable filename.");
+            println!("Assuming it's using default name...");
+            "jdk_selector_cli".to_string()
+        },
+    };
+
+    println!("USAGE:");
+    println!("\t {} init <bash|zsh|fish>", &file_name);
+    println!();
+    println!("DESCRIPTION:");
+    println!("\t This command will print script which, once evaluated by shell on startup,");
+    println!("\t - adds folder of JDK Selector to PATH so executors like java are found,");
+    println!("\t - lets shell, use and local commands change JAVA_HOME of current shell right away,");
+    println!("\t - sets JAVA_HOME again whenever current directory changes, following project files.");
+    println!();
+    println!("EXAMPLES:");
+    println!("\t Add the line below to ~/.bashrc, ~/.zshrc or ~/.config/fish/config.fish respectively.");
+    println!("\t eval \"$({} init bash)\"", &file_name);
+    println!("\t eval \"$({} init zsh)\"", &file_name);
+    println!("\t {} init fish | source", &file_name);
+    return ExitCode::SUCCESS;
+}
+
+fn show_rehash_help() -> ExitCode {
+    let file_name = match file_name() {
+        Ok(value) => value,
+        Err(..) => {
+            println!("Couldn't retrieve current executable filename.");
+            println!("Assuming it's using default name...");
+            "jdk_selector_cli".to_string()
+        },
+    };
+
+    println!("USAGE:");
+    println!("\t {} rehash", &file_name);
+    println!();
+    println!("DESCRIPTION:");
+    println!("\t This command will create executor in install directory for every tool found in bin folder");
+    println!("\t of added JDKs, like native-image of GraalVM, and remove executors no JDK provides.");
+    println!("\t Executors are links to {}.", CLI_NAME);
+    println!();
+    println!("\t It runs automatically whenever JDK information is added or removed.");
+    println!("\t Install directory must be writable, so it may have to be run with administrator privilege.");
+    return ExitCode::SUCCESS;
+}
+
+fn show_config_help() -> ExitCode {
+    let file_name = match file_name() {
+        Ok(value) => value,
+        Err(..) => {
+            println!("Couldn't retrieve current executable filename.");
+            println!("Assuming it's using default name...");
+            "jdk_selector_cli".to_string()
+        },
+    };
+
+    println!("USAGE:");
+    println!("\t {} config repair [path]", &file_name);
+    println!();
+    println!("DESCRIPTION:");
+    println!("\t Invalid config is never overwritten. It's moved aside as config.json.corrupt-<timestamp>");
+    println!("\t and new config is created instead.");
+    println!();
+    println!("\t repair will recover JDK information from the latest moved config, or from path if given,");
+    println!("\t and add it into current config. JDK information with existing names will be skipped.");
+    return ExitCode::SUCCESS;
+}
+
+fn add_jdk_info(config: &Config, config_path: &Path, name: &String, path: &str, force: bool) -> ExitCode {
+    let existed = config.jdk_info_list.iter().find(| value | value.name == *name);
+
+    if existed.is_some() {
+        println!("Name must be unique.");
+        return ExitCode::FAILURE;
+    }
+
+    let path = match force {
+        // Made absolute for the same reason as validated path, but without checking it exists
+        true => std::path::absolute(path)
+            .map(| value | value.to_string_lossy().to_string())
+            .unwrap_or(path.to_string()),
+        false => match validated_jdk_path(path) {
+            Some(value) => value,
+            None => {
+                println!("Couldn't add new JDK information. Use --force to add it anyway.");
+                return ExitCode::FAILURE;
+            },
+        },
+    };
+
+    let mut jdk_info = JdkInfo::new(name, &path);
+    match refresh_metadata(&mut jdk_info) {
+        Ok(..) => {},
+        Err(e) => {
+            println!("{}", e);
+            println!("{}", "Couldn't read JDK metadata from release file or java. It will be left empty.".yellow());
+        },
+    };
+
+    let mut updated_list: Vec<JdkInfo> = config.jdk_info_list.to_vec();
+    updated_list.push(jdk_info);
+
+    let updated_config = Config {
+        selected_jdk: config.selected_jdk.clone().or(Some(name.to_string())),
+        jdk_info_list: updated_list,
+        ..config.clone()
+    };
+
+    return match set_config(config_path, &updated_config) {
+        Ok(..) => {
+            println!("{} is successfully added.", name);
+            rehash_after_change(&updated_config);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't add new JDK information.");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Prints why path isn't acceptable, or what has been corrected
+fn validated_jdk_path(path: &str) -> Option<String> {
+    let install_dir = file_path().ok().and_then(| value | value.parent().map(| v | v.to_path_buf()));
+    let validated = match validate_jdk_home(Path::new(path), install_dir.as_deref()) {
+        Ok(value) => value,
+        Err(e) => {
+            println!("{}", e);
+            return None;
+        },
+    };
+
+    if validated.corrected {
+        println!("{}", format!("Path is corrected to {}.", validated.path.display()).yellow());
+    }
+    if let JdkKind::Jre = validated.kind {
+        println!("{}", "It looks like JRE since javac is missing. Development tools won't be available.".yellow());
+    }
+
+    return Some(validated.path.to_string_lossy().to_string());
+}
+
+fn remove_jdk_info(config: &Config, config_path: &Path, name: &String) -> ExitCode {
+    let existed = config.jdk_info_list.iter().find(| value | value.name == *name);
+
+    match existed {
+        Some(..) => {}
+        None => {
+            println!("Couldn't find JDK Information named {}.", name);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let updated_list: Vec<JdkInfo> = config.jdk_info_list.iter()
+        .filter(| value | value.name != *name)
+        .cloned()
+        .collect();
+
+    let mut selected_jdk = config.selected_jdk.clone();
+    if selected_jdk.as_ref() == Some(name) {
+        selected_jdk = updated_list.first().map(| value | value.name.to_string());
+
+        match &selected_jdk {
+            Some(value) => println!(
+                "{}",
+                format!("{} was currently in-use. Falling back to {}...", name, value).yellow(),
+            ),
+            None => println!(
+                "{}",
+                format!("{} was currently in-use. No JDK is selected now.", name).yellow(),
+            ),
+        }
+    }
+
+    let mut updated_config = Config {
+        selected_jdk,
+        jdk_info_list: updated_list,
+        ..config.clone()
+    };
+
+    // Overrides with constraint still matching other JDKs are kept
+    let dangling_tools: Vec<String> = updated_config.tool_overrides.iter()
+        .filter(| (_, selector) | find_jdk_info(config, selector).is_some_and(| value | value.name == *name))
+        .filter(| (_, selector) | find_jdk_info(&updated_config, selector).is_none())
+        .map(| (tool, _) | tool.to_string())
+        .collect();
+    for tool in dangling_tools.iter() {
+        updated_config.tool_overrides.remove(tool);
+        println!(
+            "{}",
+            format!("{} was used by override of {}. Override is removed...", name, tool).yellow(),
+        );
+    }
+
+    return match set_config(config_path, &updated_config) {
+        Ok(..) => {
+            println!("{} is successfully removed.", name);
+            rehash_after_change(&updated_config);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't remove the JDK information.");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn use_jdk(config: &Config, config_path: &Path, selector: &String) -> ExitCode {
+    // Constraints are resolved once here so global selection stays pinned to a single JDK
+    let name = match find_jdk_info(config, selector) {
+        Some(value) => value.name.to_string(),
+        None => {
+            println!("Couldn't find JDK Information named or matching {}.", selector);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let updated_config = Config {
+        selected_jdk: Some(name.to_string()),
+        ..config.clone()
+    };
+
+    return match set_config(config_path, &updated_config) {
+        Ok(..) => {
+            println!("{} is now current JDK.", name);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't set JDK.");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn show_jdk_list(config: &Config) -> ExitCode {
+    println!("Recognized JDK list");
+    println!();
+
+    for item in config.jdk_info_list.iter() {
+        let metadata = match item.metadata_summary() {
+            Some(value) => format!(" ({})", value).dimmed().to_string(),
+            None => String::new(),
+        };
+
+        if config.selected_jdk.as_ref() == Some(&item.name) {
+            println!(
+                "{} is located on \"{}\"{} {}",
+                &item.name,
+                &item.path,
+                metadata,
+                "(currently in-use)".green(),
+            );
+        } else {
+            println!(
+                "{} is located on \"{}\"{}",
+                &item.name,
+                &item.path,
+                metadata,
+            );
+        }
+    }
+
+    return ExitCode::SUCCESS;
+}
+
+fn refresh_jdk_info(config: &Config, config_path: &Path, name: Option<&String>) -> ExitCode {
+    if let Some(name) = name {
+        if config.jdk_info(name).is_none() {
+            println!("Couldn't find JDK Information named {}.", name);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let mut updated_list: Vec<JdkInfo> = config.jdk_info_list.to_vec();
+    for item in updated_list.iter_mut() {
+        if name.is_some_and(| value | *value != item.name) {
+            continue;
+        }
+
+        match refresh_metadata(item) {
+            Ok(source) => println!(
+                "{} is refreshed from {}. ({})",
+                &item.name,
+                source,
+                item.metadata_summary().unwrap_or(String::from("No metadata")),
+            ),
+            Err(e) => println!(
+                "{}",
+                format!("Couldn't read metadata of {}: {}", &item.name, e).yellow(),
+            ),
+        };
+    }
+
+    let updated_config = Config {
+        jdk_info_list: updated_list,
+        ..config.clone()
+    };
+
+    return match set_config(config_path, &updated_config) {
+        Ok(..) => ExitCode::SUCCESS,
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't save refreshed JDK information.");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn scan_jdk_info(config: &Config, config_path: &Path, args: &[String]) -> ExitCode {
+    if args.first().map(| v | v.as_str()) == Some("root") {
+        return scan_root(config, config_path, &args[1..]);
+    }
+
+    let mut roots = default_scan_roots();
+    roots.extend(config.scan_roots.iter().map(PathBuf::from));
+    let mut yes = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--yes" | "-y" => yes = true,
+            "--root" => match iter.next() {
+                Some(value) => roots.push(PathBuf::from(value)),
+                None => {
+                    show_scan_help();
+                    return ExitCode::FAILURE;
+                },
+            },
+            _ => {
+                show_scan_help();
+                return ExitCode::FAILURE;
+            },
+        }
+    }
+
+    let existing_paths: HashSet<PathBuf> = config.jdk_info_list.iter()
+        .filter_map(| value | Path::new(&value.path).canonicalize().ok())
+        .collect();
+    let mut taken_names: Vec<String> = config.jdk_info_list.iter()
+        .map(| value | value.name.to_string())
+        .collect();
+
+    let mut found_list: Vec<JdkInfo> = vec![];
+    for path in scan_jdk_homes(&roots) {
+        if existing_paths.contains(&path) {
+            continue;
+        }
+
+        let mut jdk_info = JdkInfo::new("", &path.to_string_lossy());
+        let _ = refresh_metadata(&mut jdk_info);
+        jdk_info.name = generate_name(&jdk_info, &path, &taken_names);
+        taken_names.push(jdk_info.name.to_string());
+
+        found_list.push(jdk_info);
+    }
+
+    if found_list.is_empty() {
+        println!("Couldn't find any new JDK.");
+        return ExitCode::SUCCESS;
+    }
+
+    println!("Found JDK list");
+    println!();
+    for item in found_list.iter() {
+        println!(
+            "{} is located on \"{}\" {}",
+            &item.name,
+            &item.path,
+            format!("({})", item.metadata_summary().unwrap_or(String::from("No metadata"))).dimmed(),
+        );
+    }
+    println!();
+
+    if !yes && !confirm(&format!("Add {} JDK(s)?", found_list.len())) {
+        println!("Nothing is added.");
+        return ExitCode::SUCCESS;
+    }
+
+    return add_found_jdk_info(config_path, found_list);
+}
+
+// Lock isn't held while user is answering, so config is loaded again in case another command has changed it
+fn add_found_jdk_info(config_path: &Path, found_list: Vec<JdkInfo>) -> ExitCode {
+    let _lock = match lock_config(config_path) {
+        Ok(value) => value,
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't lock config file.");
+            return ExitCode::FAILURE;
+        },
+    };
+    let config = match config(config_path) {
+        Ok(value) => value,
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't add found JDK information.");
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let existing_paths: HashSet<PathBuf> = config.jdk_info_list.iter()
+        .filter_map(| value | Path::new(&value.path).canonicalize().ok())
+        .collect();
+    let mut taken_names: Vec<String> = config.jdk_info_list.iter()
+        .map(| value | value.name.to_string())
+        .collect();
+
+    let mut added_list: Vec<JdkInfo> = vec![];
+    for mut jdk_info in found_list {
+        let path = PathBuf::from(&jdk_info.path);
+        if existing_paths.contains(&path) {
+            continue;
+        }
+        if taken_names.contains(&jdk_info.name) {
+            jdk_info.name = generate_name(&jdk_info, &path, &taken_names);
+        }
+        taken_names.push(jdk_info.name.to_string());
+
+        added_list.push(jdk_info);
+    }
+
+    if added_list.is_empty() {
+        println!("Found JDKs have been added meanwhile. Nothing is added.");
+        return ExitCode::SUCCESS;
+    }
+
+    let mut updated_list: Vec<JdkInfo> = config.jdk_info_list.to_vec();
+    updated_list.extend(added_list.iter().cloned());
+
+    let updated_config = Config {
+        selected_jdk: config.selected_jdk.clone().or(added_list.first().map(| value | value.name.to_string())),
+        jdk_info_list: updated_list,
+        ..config.clone()
+    };
+
+    return match set_config(config_path, &updated_config) {
+        Ok(..) => {
+            println!("{} JDK(s) are successfully added.", added_list.len());
+            rehash_after_change(&updated_config);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't add found JDK information.");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn scan_root(config: &Config, config_path: &Path, args: &[String]) -> ExitCode {
+    let mut scan_roots = config.scan_roots.to_vec();
+
+    match (args.first().map(| v | v.as_str()), args.get(1)) {
+        (Some("list") | None, _) => {
+            for root in default_scan_roots() {
+                println!("{} {}", root.display(), "(default)".dimmed());
+            }
+            for root in scan_roots.iter() {
+                println!("{}", root);
+            }
+            return ExitCode::SUCCESS;
+        },
+        (Some("add"), Some(path)) => {
+            // Relative paths wouldn't make sense once current directory changes
+            let absolute_path = match Path::new(path).canonicalize() {
+                Ok(value) => value.to_string_lossy().to_string(),
+                Err(e) => {
+                    println!("{}", e);
+                    println!("Couldn't find {}.", path);
+                    return ExitCode::FAILURE;
+                },
+            };
+
+            if scan_roots.contains(&absolute_path) {
+                println!("{} is already added.", absolute_path);
+                return ExitCode::SUCCESS;
+            }
+
+            println!("{} is added to scan roots.", absolute_path);
+            scan_roots.push(absolute_path);
+        },
+        (Some("remove"), Some(path)) => {
+            let absolute_path = Path::new(path).canonicalize()
+                .map(| value | value.to_string_lossy().to_string())
+                .unwrap_or(path.to_string());
+
+            let previous_len = scan_roots.len();
+            scan_roots.retain(| value | *value != *path && *value != absolute_path);
+
+            if scan_roots.len() == previous_len {
+                println!("Couldn't find scan root {}.", path);
+                return ExitCode::FAILURE;
+            }
+
+            println!("{} is removed from scan roots.", path);
+        },
+        _ => {
+            show_scan_help();
+            return ExitCode::FAILURE;
+        },
+    }
+
+    let updated_config = Config {
+        scan_roots,
+        ..config.clone()
+    };
+
+    return match set_config(config_path, &updated_config) {
+        Ok(..) => ExitCode::SUCCESS,
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't save scan roots.");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Names look like temurin-17.0.8+7, or directory name if metadata is unknown
+fn generate_name(jdk_info: &JdkInfo, path: &Path, taken_names: &[String]) -> String {
+    let base_name = match (&jdk_info.vendor, &jdk_info.version) {
+        (Some(vendor), Some(version)) => format!("{}-{}", vendor_alias(vendor), version),
+        _ => path.file_name()
+            .map(| value | value.to_string_lossy().to_string())
+            .unwrap_or(String::from("jdk")),
+    };
+
+    let mut name = base_name.to_string();
+    let mut suffix = 2;
+    while taken_names.contains(&name) {
+        name = format!("{}-{}", base_name, suffix);
+        suffix += 1;
+    }
+
+    return name;
+}
+
+fn confirm(question: &str) -> bool {
+    print!("{} [y/N] ", question);
+    let _ = io::stdout().flush();
+
+    let mut answer = String::new();
+    return match io::stdin().read_line(&mut answer) {
+        Ok(..) => matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"),
+        Err(..) => false,
+    };
+}
+
+fn repair_config(config_path: &Path, source: Option<&String>) -> ExitCode {
+    // Loading twice creates new config if invalid one has just been moved aside
+    let config = match config(config_path) {
+        Ok(value) => Ok(value),
+        Err(JsonErrorOrIOOrInvalidPathError::InvalidConfig(e, Some(quarantine_path))) => {
+            println!("Config file is invalid: {}", e);
+            println!("It has been moved to {}", quarantine_path.display());
+            config(config_path)
+        },
+        Err(e) => Err(e),
+    };
+    let config = match config {
+        Ok(value) => value,
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't repair config.");
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let source_path = match source {
+        Some(value) => PathBuf::from(value),
+        None => {
+            let _quarantined_paths = quarantined_config_paths(config_path);
+            let quarantined_paths = match _quarantined_paths {
+                Ok(value) => value,
+                Err(e) => {
+                    println!("{}", e);
+                    println!("Couldn't find invalid config to repair.");
+                    return ExitCode::FAILURE;
+                },
+            };
+
+            match quarantined_paths.last() {
+                Some(value) => value.to_path_buf(),
+                None => {
+                    println!("Couldn't find invalid config to repair.");
+                    return ExitCode::FAILURE;
+                },
+            }
+        },
+    };
+
+    let raw = match fs::read_to_string(&source_path) {
+        Ok(value) => value,
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't read {}.", source_path.display());
+            return ExitCode::FAILURE;
+        },
+    };
+    let recovered = recover_config(&raw);
+
+    let mut updated_list: Vec<JdkInfo> = config.jdk_info_list.to_vec();
+    for item in recovered.jdk_info_list {
+        if config.jdk_info(&item.name).is_some() {
+            println!("{} already exists. Skipping...", &item.name);
+            continue;
+        }
+
+        println!("{} is recovered. (\"{}\")", &item.name, &item.path);
+        updated_list.push(item);
+    }
+
+    let selected_jdk = config.selected_jdk.clone().or(
+        recovered.selected_jdk.filter(| name | updated_list.iter().any(| value | value.name == *name)),
+    );
+
+    let updated_config = Config {
+        selected_jdk,
+        jdk_info_list: updated_list,
+        ..config.clone()
+    };
+
+    return match set_config(config_path, &updated_config) {
+        Ok(..) => {
+            println!("Config is repaired from {}.", source_path.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't repair config.");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn local_jdk(config: &Config, name: Option<&String>) -> ExitCode {
+    let current_dir = match env::current_dir() {
+        Ok(value) => value,
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't retrieve current directory.");
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let name = match name.map(| value | value.as_str()) {
+        Some("--unset") => {
+            return match fs::remove_file(current_dir.join(PROJECT_FILE_NAME)) {
+                Ok(..) => {
+                    println!("{} is removed.", PROJECT_FILE_NAME);
+                    ExitCode::SUCCESS
+                },
+                Err(e) => {
+                    println!("{}", e);
+                    println!("Couldn't remove {}.", PROJECT_FILE_NAME);
+                    ExitCode::FAILURE
+                },
+            };
+        },
+        Some(value) => value,
+        None => return show_local_jdk(&current_dir),
+    };
+
+    // Selector is written as is so project can keep constraint like 17 instead of local JDK name
+    let jdk_info = match find_jdk_info(config, name) {
+        Some(value) => value,
+        None => {
+            println!("Couldn't find JDK Information named or matching {}.", name);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    return match write_project_file(&current_dir, name) {
+        Ok(path) => {
+            println!("{} is now JDK for {} (currently {}).", name, path.display(), jdk_info.name);
+            ExitCode::SUCCESS
+        },
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't write {}.", PROJECT_FILE_NAME);
+            ExitCode::FAILURE
+        },
+    };
+}
+
+fn show_local_jdk(current_dir: &Path) -> ExitCode {
+    let path = match find_project_file(current_dir) {
+        Some(value) => value,
+        None => {
+            println!("Couldn't find {} from current directory.", PROJECT_FILE_NAME);
+            return ExitCode::FAILURE;
+        },
+    };
+
+    return match read_project_file(&path) {
+        Ok(Some(value)) => {
+            println!("{} (set by {})", value, path.display());
+            ExitCode::SUCCESS
+        },
+        Ok(None) => {
+            println!("{} is empty.", path.display());
+            ExitCode::FAILURE
+        },
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't read {}.", path.display());
+            ExitCode::FAILURE
+        },
+    };
+}
+
+// Output is meant to be evaluated by shell, so anything else must go to stderr
+fn shell_jdk(config: &Config, args: &[String]) -> ExitCode {
+    let mut shell = Shell::detect();
+    let mut name: Option<&String> = None;
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        if arg == "--shell" {
+            let _shell = iter.next().and_then(| value | Shell::from_name(value));
+            shell = match _shell {
+                Some(value) => value,
+                None => {
+                    eprintln!("Unknown shell. Supported shells are bash, zsh, fish, powershell and cmd.");
+                    return ExitCode::FAILURE;
+                },
+            };
+        } else {
+            name = Some(arg);
+        }
+    }
+
+    let name = match name.map(| value | value.as_str()) {
+        Some("--unset") => {
+            println!("{}", shell.unset(JDK_OVERRIDE_ENV));
+            return ExitCode::SUCCESS;
+        },
+        Some(value) => value,
+        None => {
+            return match env_override() {
+                Some(value) => {
+                    println!("{}", value);
+                    ExitCode::SUCCESS
+                },
+                None => {
+                    eprintln!("{} isn't set in current shell.", JDK_OVERRIDE_ENV);
+                    ExitCode::FAILURE
+                },
+            };
+        },
+    };
+
+    if find_jdk_info(config, name).is_none() {
+        eprintln!("Couldn't find JDK Information named or matching {}.", name);
+        return ExitCode::FAILURE;
+    }
+
+    println!("{}", shell.export(JDK_OVERRIDE_ENV, name));
+    return ExitCode::SUCCESS;
+}
+
+fn prefer_vendors(config: &Config, config_path: &Path, args: &[String]) -> ExitCode {
+    if args.is_empty() {
+        if config.preferred_vendors.is_empty() {
+            println!("No vendor is preferred.");
+        }
+        for vendor in config.preferred_vendors.iter() {
+            println!("{}", vendor);
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    let preferred_vendors: Vec<String> = match args.first().map(| value | value.as_str()) {
+        Some("--clear") => vec![],
+        _ => args.iter().map(| value | value.to_lowercase()).collect(),
+    };
+
+    let updated_config = Config {
+        preferred_vendors,
+        ..config.clone()
+    };
+
+    return match set_config(config_path, &updated_config) {
+        Ok(..) => {
+            println!("Preferred vendors are updated.");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't update preferred vendors.");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn change_setting(config: &Config, config_path: &Path, args: &[String]) -> ExitCode {
+    let settings = [
+        ("tool_fallback", config.tool_fallback),
+        ("export_java_home", config.export_java_home),
+    ];
+
+    let key = match args.first() {
+        Some(value) => value.as_str(),
+        None => {
+            for (key, value) in settings.iter() {
+                println!("{} = {}", key, value);
+            }
+            return ExitCode::SUCCESS;
+        },
+    };
+
+    let current = match settings.iter().find(| (name, _) | *name == key) {
+        Some((_, value)) => *value,
+        None => {
+            println!("Unknown setting {}.", key);
+            show_setting_help();
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let value = match args.get(1).map(| value | value.as_str()) {
+        Some("true") => true,
+        Some("false") => false,
+        Some(..) => {
+            println!("Value must be true or false.");
+            return ExitCode::FAILURE;
+        },
+        None => {
+            println!("{} = {}", key, current);
+            return ExitCode::SUCCESS;
+        },
+    };
+
+    let mut updated_config = config.clone();
+    match key {
+        "tool_fallback" => updated_config.tool_fallback = value,
+        "export_java_home" => updated_config.export_java_home = value,
+        _ => unreachable!(),
+    };
+
+    return match set_config(config_path, &updated_config) {
+        Ok(..) => {
+            println!("{} is now {}.", key, value);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't change setting.");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn set_tool_override(config: &Config, config_path: &Path, tool: &str, selector: &str) -> ExitCode {
+    let jdk_info = match find_jdk_info(config, selector) {
+        Some(value) => value,
+        None => {
+            println!("Couldn't find JDK Information named or matching {}.", selector);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut updated_config = config.clone();
+    updated_config.tool_overrides.insert(tool.to_string(), selector.to_string());
+
+    return match set_config(config_path, &updated_config) {
+        Ok(..) => {
+            println!("{} will now use {} (currently {}).", tool, selector, jdk_info.name);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't set override of {}.", tool);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn unset_tool_override(config: &Config, config_path: &Path, tool: &str) -> ExitCode {
+    let mut updated_config = config.clone();
+    if updated_config.tool_overrides.remove(tool).is_none() {
+        println!("{} doesn't have override.", tool);
+        return ExitCode::FAILURE;
+    }
+
+    return match set_config(config_path, &updated_config) {
+        Ok(..) => {
+            println!("Override of {} is removed.", tool);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't remove override of {}.", tool);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn show_tool_overrides(config: &Config) -> ExitCode {
+    if config.tool_overrides.is_empty() {
+        println!("No tool has override.");
+        return ExitCode::SUCCESS;
+    }
+
+    for (tool, selector) in config.tool_overrides.iter() {
+        match find_jdk_info(config, selector) {
+            Some(jdk_info) if jdk_info.name != *selector => {
+                println!("{} uses {} {}", tool, selector, format!("(currently {})", jdk_info.name).dimmed());
+            },
+            Some(..) => println!("{} uses {}", tool, selector),
+            None => println!("{} uses {} {}", tool, selector, "(not found)".red()),
+        }
+    }
+
+    return ExitCode::SUCCESS;
+}
+
+fn jdk_env(config: &Config, config_path: &Path, args: &[String]) -> ExitCode {
+    let action = args.first().map(| v | v.as_str());
+    let jdk_info = match args.get(1) {
+        Some(name) => match config.jdk_info(name) {
+            Some(value) => value,
+            None => {
+                println!("Couldn't find JDK Information named {}.", name);
+                return ExitCode::FAILURE;
+            },
+        },
+        None => {
+            show_env_help();
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let mut updated_jdk_info = jdk_info.clone();
+    match (action, args.get(2), args.get(3)) {
+        (Some("set"), Some(key), Some(value)) => {
+            updated_jdk_info.env.insert(key.to_string(), value.to_string());
+        },
+        (Some("unset"), Some(key), None) => {
+            if updated_jdk_info.env.remove(key).is_none() {
+                println!("{} doesn't have {}.", jdk_info.name, key);
+                return ExitCode::FAILURE;
+            }
+        },
+        (Some("list"), None, None) => {
+            if jdk_info.env.is_empty() {
+                println!("{} doesn't have environment variables.", jdk_info.name);
+            }
+            for (key, value) in jdk_info.env.iter() {
+                println!("{}={}", key, value);
+            }
+            return ExitCode::SUCCESS;
+        },
+        _ => {
+            show_env_help();
+            return ExitCode::FAILURE;
+        },
+    };
+
+    return match update_jdk_info(config, config_path, updated_jdk_info) {
+        Ok(..) => {
+            println!("Environment variables of {} are updated.", jdk_info.name);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't update environment variables.");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn jdk_options(config: &Config, config_path: &Path, args: &[String]) -> ExitCode {
+    let action = args.first().map(| v | v.as_str());
+    let jdk_info = match args.get(1) {
+        Some(name) => match config.jdk_info(name) {
+            Some(value) => value,
+            None => {
+                println!("Couldn't find JDK Information named {}.", name);
+                return ExitCode::FAILURE;
+            },
+        },
+        None => {
+            show_options_help();
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let mut updated_jdk_info = jdk_info.clone();
+    match action {
+        Some("set") if args.len() > 2 => updated_jdk_info.java_options = args[2..].to_vec(),
+        Some("clear") if args.len() == 2 => updated_jdk_info.java_options.clear(),
+        Some("list") if args.len() == 2 => {
+            if jdk_info.java_options.is_empty() {
+                println!("{} doesn't have JVM options.", jdk_info.name);
+            }
+            for option in jdk_info.java_options.iter() {
+                println!("{}", option);
+            }
+            return ExitCode::SUCCESS;
+        },
+        _ => {
+            show_options_help();
+            return ExitCode::FAILURE;
+        },
+    };
+
+    return match update_jdk_info(config, config_path, updated_jdk_info) {
+        Ok(..) => {
+            println!("JVM options of {} are updated.", jdk_info.name);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't update JVM options.");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Replaces JDK information having same name
+fn update_jdk_info(
+    config: &Config,
+    config_path: &Path,
+    jdk_info: JdkInfo,
+) -> Result<(), JsonErrorOrIOOrInvalidPathError> {
+    let updated_list: Vec<JdkInfo> = config.jdk_info_list.iter()
+        .map(| value | if value.name == jdk_info.name { jdk_info.clone() } else { value.clone() })
+        .collect();
+
+    let updated_config = Config {
+        jdk_info_list: updated_list,
+        ..config.clone()
+    };
+
+    return set_config(config_path, &updated_config);
+}
+
+fn rehash_executors(config: &Config) -> ExitCode {
+    return match rehash_and_report(config) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => {
+            println!("Executors are up to date.");
+            ExitCode::SUCCESS
+        },
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't rehash executors.");
+            ExitCode::FAILURE
+        },
+    };
+}
+
+// Config is already saved when this runs, so failing to rehash is only worth a warning
+fn rehash_after_change(config: &Config) {
+    if let Err(e) = rehash_and_report(config) {
+        println!("{}", format!("Couldn't rehash executors: {}", e).yellow());
+        println!("{}", format!("Run '{} rehash' with write permission to install directory.", CLI_NAME).yellow());
+    }
+}
+
+// Returns whether any executor is created or removed
+fn rehash_and_report(config: &Config) -> io::Result<bool> {
+    let result = rehash(&file_path()?, &tool_file_names(config))?;
+
+    if !result.created.is_empty() {
+        println!("Created executors: {}", result.created.join(", "));
+    }
+    if !result.removed.is_empty() {
+        println!("Removed executors: {}", result.removed.join(", "));
+    }
+
+    return Ok(!result.created.is_empty() || !result.removed.is_empty());
+}
+
+fn which_tool(config: &Config, args: &[String]) -> ExitCode {
+    let explain = args.iter().any(| value | value == "--explain");
+    let tool = match args.iter().find(| value | *value != "--explain") {
+        Some(value) => value.strip_suffix(env::consts::EXE_SUFFIX).unwrap_or(value),
+        None => {
+            show_which_help();
+            return ExitCode::FAILURE;
+        },
+    };
+    let file_name = format!("{}{}", tool, env::consts::EXE_SUFFIX);
+
+    let current_dir = match env::current_dir() {
+        Ok(value) => value,
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't retrieve current directory.");
+            return ExitCode::FAILURE;
+        },
+    };
+
+    if explain {
+        explain_layers(config, &current_dir, tool);
+    }
+
+    return match resolve_tool_binary(config, &current_dir, &file_name) {
+        Ok(resolution) => {
+            if explain {
+                if let Some(jdk_info) = resolution.fallback_from {
+                    println!(
+                        "{} doesn't have {}, so {} providing it is used since tool_fallback is enabled.",
+                        jdk_info.name,
+                        tool,
+                        resolution.selection.jdk_info.name,
+                    );
+                }
+                println!("{} is selected by {}.", resolution.selection.jdk_info, resolution.selection.source);
+                println!();
+            }
+            println!("{}", resolution.binary.display());
+            ExitCode::SUCCESS
+        },
+        Err(e) => {
+            println!("{}", e);
+            ExitCode::FAILURE
+        },
+    };
+}
+
+fn explain_layers(config: &Config, current_dir: &Path, tool: &str) {
+    println!("Layers considered for {} in {}, in order of priority:", tool, current_dir.display());
+
+    let mut winner: Option<String> = None;
+    for (index, layer) in layers(config, current_dir, Some(tool)).into_iter().enumerate() {
+        let state = match (&layer.found, &winner) {
+            (Err(e), None) => format!("{}", e).red().to_string(),
+            (Err(e), Some(..)) => format!("{} (ignored)", e).dimmed().to_string(),
+            (Ok(None), _) => "not set".dimmed().to_string(),
+            (Ok(Some((source, selector))), Some(winner)) => {
+                format!("'{}' from {}, shadowed by {}", selector, source, winner).dimmed().to_string()
+            },
+            (Ok(Some((source, selector))), None) => match find_jdk_info(config, selector) {
+                Some(jdk_info) => format!("'{}' from {} matches {} (applied)", selector, source, jdk_info.name)
+                    .green()
+                    .to_string(),
+                None => format!("'{}' from {} doesn't match any JDK (applied)", selector, source)
+                    .red()
+                    .to_string(),
+            },
+        };
+
+        println!("\t {}. {}: {}", index + 1, layer.kind, state);
+
+        if winner.is_none() && !matches!(layer.found, Ok(None)) {
+            winner = Some(layer.kind.to_string());
+        }
+    }
+
+    println!();
+}
+
+fn exec_command(config: &Config, args: &[String]) -> ExitCode {
+    let (selector, command) = match args {
+        [selector, separator, _, ..] if separator == "--" => (selector, &args[2..]),
+        _ => {
+            show_exec_help();
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let jdk_info = match find_jdk_info(config, selector) {
+        Some(value) => value,
+        None => {
+            println!("Couldn't find JDK Information named or matching {}.", selector);
+            return ExitCode::FAILURE;
+        },
+    };
+    trace!("{} is selected by exec", jdk_info);
+
+    return run_command(jdk_command(jdk_info, command));
+}
+
+fn run_command_matrix(config: &Config, args: &[String]) -> ExitCode {
+    let separator = args.iter().position(| value | value == "--");
+    let (options, command) = match separator {
+        Some(index) if index + 1 < args.len() => (&args[..index], &args[index + 1..]),
+        _ => {
+            show_matrix_help();
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let mut filter: Option<VersionConstraint> = None;
+    let mut jobs: usize = 1;
+    let mut log_dir = PathBuf::from(MATRIX_LOG_DIR);
+    let mut junit_path: Option<PathBuf> = None;
+    let mut json_path: Option<PathBuf> = None;
+
+    let mut iter = options.iter();
+    while let Some(option) = iter.next() {
+        let value = match iter.next() {
+            Some(value) => value,
+            None => {
+                show_matrix_help();
+                return ExitCode::FAILURE;
+            },
+        };
+
+        match option.as_str() {
+            "--filter" => match VersionConstraint::parse(value) {
+                Ok(constraint) => filter = Some(constraint),
+                Err(e) => {
+                    println!("{}", e);
+                    println!("Couldn't parse filter.");
+                    return ExitCode::FAILURE;
+                },
+            },
+            "--jobs" => match value.parse::<usize>() {
+                Ok(count) if count > 0 => jobs = count,
+                _ => {
+                    println!("--jobs must be a positive number but {} is given.", value);
+                    return ExitCode::FAILURE;
+                },
+            },
+            "--logs" => log_dir = PathBuf::from(value),
+            "--junit" => junit_path = Some(PathBuf::from(value)),
+            "--json" => json_path = Some(PathBuf::from(value)),
+            _ => {
+                show_matrix_help();
+                return ExitCode::FAILURE;
+            },
+        };
+    }
+
+    let jdk_info_list: Vec<&JdkInfo> = config.jdk_info_list.iter()
+        .filter(| value | filter.as_ref().is_none_or(| constraint | constraint.matches(value)))
+        .collect();
+    if jdk_info_list.is_empty() {
+        println!("No JDK information matches the filter.");
+        return ExitCode::FAILURE;
+    }
+
+    let names: Vec<&str> = jdk_info_list.iter().map(| value | value.name.as_str()).collect();
+    println!("Running '{}' with {}", command.join(" "), names.join(", "));
+    println!();
+
+    let results = match run_matrix(&jdk_info_list, command, jobs, &log_dir) {
+        Ok(value) => value,
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't create log directory {}.", log_dir.display());
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let name_width = names.iter().map(| value | value.len()).max().unwrap_or(0).max("JDK".len());
+    let versions: Vec<&str> = jdk_info_list.iter().map(| value | value.version.as_deref().unwrap_or("-")).collect();
+    let version_width = versions.iter().map(| value | value.len()).max().unwrap_or(0).max("VERSION".len());
+
+    println!();
+    println!("{:<name_width$}  {:<version_width$}  {:<6}  {:>8}  LOG", "JDK", "VERSION", "RESULT", "DURATION");
+    for (result, version) in results.iter().zip(versions.iter()) {
+        // Padding is applied before coloring since escape codes would count as width
+        let state = format!("{:<6}", if result.passed() { "PASS" } else { "FAIL" });
+        println!(
+            "{:<name_width$}  {:<version_width$}  {}  {:>8}  {}",
+            result.jdk_info.name,
+            version,
+            if result.passed() { state.green() } else { state.red() },
+            format_duration(result.duration),
+            result.log_path.display(),
+        );
+    }
+
+    let passed = results.iter().filter(| value | value.passed()).count();
+    println!();
+    println!("{} passed, {} failed", passed, results.len() - passed);
+
+    let mut succeeded = passed == results.len();
+    if let Some(path) = &junit_path {
+        if let Err(e) = write_junit_report(path, &results, command) {
+            println!("{}", e);
+            println!("Couldn't write JUnit report to {}.", path.display());
+            succeeded = false;
+        }
+    }
+    if let Some(path) = &json_path {
+        if let Err(e) = write_json_report(path, &results, command) {
+            println!("{}", e);
+            println!("Couldn't write JSON report to {}.", path.display());
+            succeeded = false;
+        }
+    }
+
+    return if succeeded { ExitCode::SUCCESS } else { ExitCode::FAILURE };
+}
+
+// Output is meant to be evaluated by shell, so anything else must go to stderr
+fn init_shell(config: &Config, args: &[String]) -> ExitCode {
+    let shell_name = match args.first() {
+        Some(value) => value.as_str(),
+        None => {
+            show_init_help();
+            return ExitCode::FAILURE;
+        },
+    };
+
+    // Run by the script itself to find JAVA_HOME for current directory
+    if args.get(1).map(| value | value.as_str()) == Some("--export") {
+        return export_java_home(config, shell_name);
+    }
+
+    let install_dir = match file_path() {
+        Ok(value) => value.parent().map(| value | value.to_string_lossy().to_string()),
+        Err(..) => None,
+    };
+    let install_dir = match install_dir {
+        Some(value) => value,
+        None => {
+            eprintln!("Couldn't retrieve folder JDK Selector is installed in.");
+            return ExitCode::FAILURE;
+        },
+    };
+
+    return match init_script(shell_name, CLI_NAME, &install_dir) {
+        Some(value) => {
+            print!("{}", value);
+            ExitCode::SUCCESS
+        },
+        None => {
+            eprintln!("Unknown shell. Supported shells are bash, zsh and fish.");
+            ExitCode::FAILURE
+        },
+    };
+}
+
+// JAVA_HOME is left as is when nothing is selected, so shells without any selection keep working
+fn export_java_home(config: &Config, shell_name: &str) -> ExitCode {
+    let shell = match Shell::from_name(shell_name) {
+        Some(value) => value,
+        None => {
+            eprintln!("Unknown shell. Supported shells are bash, zsh and fish.");
+            return ExitCode::FAILURE;
+        },
+    };
+    let current_dir = match env::current_dir() {
+        Ok(value) => value,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("Couldn't retrieve current directory.");
+            return ExitCode::FAILURE;
+        },
+    };
+
+    return match resolve(config, &current_dir) {
+        Ok(selection) => {
+            println!("{}", shell.export("JAVA_HOME", &selection.jdk_info.path));
+            ExitCode::SUCCESS
+        },
+        Err(ResolveError::NotSelected) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("JAVA_HOME is left unchanged.");
+            ExitCode::FAILURE
+        },
+    };
+}
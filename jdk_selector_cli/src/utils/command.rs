@@ -0,0 +1,21 @@
+use std::process::Command;
+use jdk_selector_shared::models::jdk_info::JdkInfo;
+use jdk_selector_shared::utils::resolver::JDK_OVERRIDE_ENV;
+
+// Command whose JAVA_HOME, PATH and JDK_SELECTOR_JDK point at given JDK, along with its own environment variables.
+// command must have at least the program
+pub fn jdk_command(jdk_info: &JdkInfo, command: &[String]) -> Command {
+    let mut process = Command::new(&command[0]);
+    process.args(&command[1..])
+        .envs(&jdk_info.env)
+        .env("JAVA_HOME", &jdk_info.path)
+        // Exact name so executors run by the command never pick another JDK matching the same constraint
+        .env(JDK_OVERRIDE_ENV, &jdk_info.name);
+
+    // PATH is left as is if JDK path can't be joined to it
+    if let Some(value) = jdk_info.prepended_path() {
+        process.env("PATH", value);
+    }
+
+    return process;
+}
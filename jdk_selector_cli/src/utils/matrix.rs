@@ -0,0 +1,251 @@
+use std::collections::HashSet;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use jdk_selector_shared::models::jdk_info::JdkInfo;
+use crate::utils::command::jdk_command;
+
+pub enum Outcome {
+    Passed,
+    // Exit code, or None if terminated by signal
+    Failed(Option<i32>),
+    // Command couldn't be started at all
+    Error(String),
+}
+
+pub struct MatrixResult<'a> {
+    pub jdk_info: &'a JdkInfo,
+    pub outcome: Outcome,
+    pub duration: Duration,
+    pub log_path: PathBuf,
+}
+
+impl MatrixResult<'_> {
+    pub fn passed(&self) -> bool {
+        return matches!(self.outcome, Outcome::Passed);
+    }
+
+    pub fn describe(&self) -> String {
+        return match &self.outcome {
+            Outcome::Passed => "passed".to_string(),
+            Outcome::Failed(Some(code)) => format!("failed with exit code {}", code),
+            Outcome::Failed(None) => "terminated by signal".to_string(),
+            Outcome::Error(e) => format!("couldn't start: {}", e),
+        };
+    }
+}
+
+// Runs command once per JDK with at most jobs of them at the same time.
+// Results are in the same order as jdk_info_list regardless of which finished first
+pub fn run_matrix<'a>(
+    jdk_info_list: &[&'a JdkInfo],
+    command: &[String],
+    jobs: usize,
+    log_dir: &Path,
+) -> io::Result<Vec<MatrixResult<'a>>> {
+    fs::create_dir_all(log_dir)?;
+    let log_names = log_file_names(jdk_info_list);
+
+    let next = Mutex::new(0usize);
+    let results: Mutex<Vec<Option<MatrixResult>>> = Mutex::new(jdk_info_list.iter().map(| _ | None).collect());
+
+    thread::scope(| scope | {
+        for _ in 0..jobs.clamp(1, jdk_info_list.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = {
+                    let mut next = next.lock().unwrap();
+                    *next += 1;
+                    *next - 1
+                };
+                let jdk_info = match jdk_info_list.get(index) {
+                    Some(value) => *value,
+                    None => break,
+                };
+
+                let result = run_one(jdk_info, command, log_dir.join(&log_names[index]));
+                println!("{} {}", jdk_info.name, result.describe());
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    return Ok(results.into_inner().unwrap().into_iter().flatten().collect());
+}
+
+fn run_one<'a>(jdk_info: &'a JdkInfo, command: &[String], log_path: PathBuf) -> MatrixResult<'a> {
+    let started = Instant::now();
+
+    let outcome = match spawn_logged(jdk_info, command, &log_path) {
+        Ok(status) if status.success() => Outcome::Passed,
+        Ok(status) => Outcome::Failed(status.code()),
+        Err(e) => {
+            // Otherwise the log would be left empty with no clue why
+            let _ = fs::write(&log_path, format!("{}\n", e));
+            Outcome::Error(e.to_string())
+        },
+    };
+
+    return MatrixResult { jdk_info, outcome, duration: started.elapsed(), log_path };
+}
+
+// Stdout and stderr share one log file so their lines stay interleaved as printed
+fn spawn_logged(jdk_info: &JdkInfo, command: &[String], log_path: &Path) -> io::Result<std::process::ExitStatus> {
+    let log = File::create(log_path)?;
+
+    return jdk_command(jdk_info, command)
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log)
+        .status();
+}
+
+// Names like temurin-17 and temurin/17 become the same once made safe, so later ones get a number appended.
+// Compared case-insensitively since so are file systems of Windows and macOS
+fn log_file_names(jdk_info_list: &[&JdkInfo]) -> Vec<String> {
+    let mut taken: HashSet<String> = HashSet::new();
+
+    return jdk_info_list.iter()
+        .map(| jdk_info | {
+            let base = file_safe_name(&jdk_info.name);
+            let mut name = base.to_string();
+            let mut number = 2;
+            while !taken.insert(name.to_lowercase()) {
+                name = format!("{}-{}", base, number);
+                number += 1;
+            }
+
+            format!("{}.log", name)
+        })
+        .collect();
+}
+
+// JDK names are given by user and may contain path separators
+fn file_safe_name(name: &str) -> String {
+    return name.chars()
+        .map(| value | if value.is_ascii_alphanumeric() || "-_.".contains(value) { value } else { '_' })
+        .collect();
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs_f64();
+    if seconds < 60.0 {
+        return format!("{:.1}s", seconds);
+    }
+
+    return format!("{}m {:02}s", duration.as_secs() / 60, duration.as_secs() % 60);
+}
+
+pub fn write_junit_report(path: &Path, results: &[MatrixResult], command: &[String]) -> io::Result<()> {
+    let failures = results.iter().filter(| value | matches!(value.outcome, Outcome::Failed(..))).count();
+    let errors = results.iter().filter(| value | matches!(value.outcome, Outcome::Error(..))).count();
+    let total: f64 = results.iter().map(| value | value.duration.as_secs_f64()).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\">\n",
+        escape_xml(&command.join(" ")),
+        results.len(),
+        failures,
+        errors,
+        total,
+    ));
+
+    for result in results {
+        xml.push_str(&format!(
+            "  <testcase classname=\"jdk-matrix\" name=\"{}\" time=\"{:.3}\">\n",
+            escape_xml(&result.jdk_info.name),
+            result.duration.as_secs_f64(),
+        ));
+        match &result.outcome {
+            Outcome::Passed => {},
+            Outcome::Failed(..) => xml.push_str(&format!("    <failure message=\"{}\"/>\n", escape_xml(&result.describe()))),
+            Outcome::Error(..) => xml.push_str(&format!("    <error message=\"{}\"/>\n", escape_xml(&result.describe()))),
+        };
+        xml.push_str(&format!("    <system-out>Log: {}</system-out>\n", escape_xml(&result.log_path.display().to_string())));
+        xml.push_str("  </testcase>\n");
+    }
+
+    xml.push_str("</testsuite>\n");
+    return fs::write(path, xml);
+}
+
+fn escape_xml(raw: &str) -> String {
+    return raw.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;");
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    command: &'a [String],
+    passed: usize,
+    failed: usize,
+    results: Vec<JsonResult<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonResult<'a> {
+    name: &'a str,
+    path: &'a str,
+    version: Option<&'a str>,
+    passed: bool,
+    exit_code: Option<i32>,
+    error: Option<&'a str>,
+    duration_ms: u128,
+    log: String,
+}
+
+pub fn write_json_report(path: &Path, results: &[MatrixResult], command: &[String]) -> io::Result<()> {
+    let passed = results.iter().filter(| value | value.passed()).count();
+    let report = JsonReport {
+        command,
+        passed,
+        failed: results.len() - passed,
+        results: results.iter().map(| value | JsonResult {
+            name: &value.jdk_info.name,
+            path: &value.jdk_info.path,
+            version: value.jdk_info.version.as_deref(),
+            passed: value.passed(),
+            exit_code: match &value.outcome {
+                Outcome::Passed => Some(0),
+                Outcome::Failed(code) => *code,
+                Outcome::Error(..) => None,
+            },
+            error: match &value.outcome {
+                Outcome::Error(e) => Some(e),
+                _ => None,
+            },
+            duration_ms: value.duration.as_millis(),
+            log: value.log_path.display().to_string(),
+        }).collect(),
+    };
+
+    let raw = serde_json::to_string_pretty(&report).map_err(io::Error::other)?;
+    return fs::write(path, raw);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_file_names_are_unique() {
+        let jdk_info_list: Vec<JdkInfo> = ["temurin-17", "temurin_17", "a/b", "a:b", "A_b", "a_b-2", "21"].iter()
+            .map(| value | JdkInfo::new(value, "/opt/jdk"))
+            .collect();
+        let references: Vec<&JdkInfo> = jdk_info_list.iter().collect();
+
+        assert_eq!(
+            log_file_names(&references),
+            ["temurin-17.log", "temurin_17.log", "a_b.log", "a_b-2.log", "A_b-3.log", "a_b-2-2.log", "21.log"],
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod command;
+pub mod matrix;
+pub mod shell;
+pub mod shell_init;
@@ -0,0 +1,64 @@
+use std::env;
+use std::path::Path;
+
+pub enum Shell {
+    Posix,
+    Fish,
+    Powershell,
+    Cmd,
+}
+
+impl Shell {
+    pub fn from_name(name: &str) -> Option<Shell> {
+        return match name {
+            "bash" | "zsh" | "sh" | "dash" | "ksh" => Some(Shell::Posix),
+            "fish" => Some(Shell::Fish),
+            "powershell" | "pwsh" => Some(Shell::Powershell),
+            "cmd" => Some(Shell::Cmd),
+            _ => None,
+        };
+    }
+
+    // Guesses shell which launched cli, falling back to POSIX shell
+    pub fn detect() -> Shell {
+        if let Some(shell) = env::var_os("SHELL")
+            .and_then(| value | Path::new(&value).file_stem().map(| v | v.to_string_lossy().to_string()))
+            .and_then(| value | Shell::from_name(&value)) {
+            return shell;
+        }
+
+        if cfg!(windows) {
+            return if env::var_os("PSModulePath").is_some() { Shell::Powershell } else { Shell::Cmd };
+        }
+
+        return Shell::Posix;
+    }
+
+    pub fn export(&self, key: &str, value: &str) -> String {
+        return match self {
+            Shell::Posix => format!("export {}={}", key, self.quote(value)),
+            Shell::Fish => format!("set -gx {} {}", key, self.quote(value)),
+            Shell::Powershell => format!("$env:{} = {}", key, self.quote(value)),
+            Shell::Cmd => format!("set \"{}={}\"", key, value),
+        };
+    }
+
+    // Literal string which isn't expanded by shell
+    pub fn quote(&self, value: &str) -> String {
+        return match self {
+            Shell::Posix => format!("'{}'", value.replace('\'', "'\\''")),
+            Shell::Fish => format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'")),
+            Shell::Powershell => format!("'{}'", value.replace('\'', "''")),
+            Shell::Cmd => format!("\"{}\"", value),
+        };
+    }
+
+    pub fn unset(&self, key: &str) -> String {
+        return match self {
+            Shell::Posix => format!("unset {}", key),
+            Shell::Fish => format!("set -e {}", key),
+            Shell::Powershell => format!("Remove-Item Env:{} -ErrorAction SilentlyContinue", key),
+            Shell::Cmd => format!("set {}=", key),
+        };
+    }
+}
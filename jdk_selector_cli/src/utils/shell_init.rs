@@ -0,0 +1,106 @@
+use crate::utils::shell::Shell;
+
+// Wrapper evaluates output of shell itself, since child process can't change environment of its shell.
+// Commands changing selection refresh JAVA_HOME right away instead of waiting for next cd
+const POSIX_WRAPPER: &str = r#"_jdk_selector_export() {
+    eval "$(command {cli} init {shell} --export)"
+}
+
+{cli}() {
+    case "$1" in
+        shell)
+            if [ "$#" -gt 1 ]; then
+                shift
+                local script
+                script="$(command {cli} shell --shell {shell} "$@")" || return
+                eval "$script"
+                _jdk_selector_export
+                return
+            fi
+            ;;
+        use|local)
+            command {cli} "$@" || return
+            _jdk_selector_export
+            return
+            ;;
+    esac
+    command {cli} "$@"
+}
+"#;
+
+// bash has no hook for directory change, so prompt checks whether it has changed
+const BASH_HOOK: &str = r#"_jdk_selector_hook() {
+    local previous_exit=$?
+    if [ "${_jdk_selector_dir:-}" != "$PWD" ]; then
+        _jdk_selector_dir="$PWD"
+        _jdk_selector_export
+    fi
+    return $previous_exit
+}
+
+case ";${PROMPT_COMMAND:-};" in
+    *";_jdk_selector_hook;"*) ;;
+    *) PROMPT_COMMAND="_jdk_selector_hook${PROMPT_COMMAND:+;$PROMPT_COMMAND}" ;;
+esac
+"#;
+
+const ZSH_HOOK: &str = r#"autoload -Uz add-zsh-hook
+add-zsh-hook chpwd _jdk_selector_export
+_jdk_selector_export
+"#;
+
+const FISH_SCRIPT: &str = r#"if not contains -- {install_dir} $PATH
+    set -gx PATH {install_dir} $PATH
+end
+
+function _jdk_selector_export
+    command {cli} init fish --export | source
+end
+
+function _jdk_selector_hook --on-variable PWD
+    _jdk_selector_export
+end
+
+function {cli}
+    switch "$argv[1]"
+        case shell
+            if test (count $argv) -gt 1
+                set -l script (command {cli} shell --shell fish $argv[2..-1]); or return
+                string join \n $script | source
+                _jdk_selector_export
+                return
+            end
+        case use local
+            command {cli} $argv; or return
+            _jdk_selector_export
+            return
+    end
+    command {cli} $argv
+end
+
+_jdk_selector_export
+"#;
+
+// Snippet to be evaluated on shell startup, or None if shell isn't supported
+pub fn init_script(shell_name: &str, cli_name: &str, install_dir: &str) -> Option<String> {
+    let (script, shell) = match shell_name {
+        "bash" => (posix_script(BASH_HOOK), Shell::Posix),
+        "zsh" => (posix_script(ZSH_HOOK), Shell::Posix),
+        "fish" => (FISH_SCRIPT.to_string(), Shell::Fish),
+        _ => return None,
+    };
+
+    return Some(script.replace("{install_dir}", &shell.quote(install_dir))
+        .replace("{cli}", cli_name)
+        .replace("{shell}", shell_name));
+}
+
+fn posix_script(hook: &str) -> String {
+    let path = r#"case ":${PATH}:" in
+    *:{install_dir}:*) ;;
+    *) export PATH={install_dir}:"${PATH}" ;;
+esac
+"#;
+
+    return format!("{}\n{}\n{}", path, POSIX_WRAPPER, hook);
+}
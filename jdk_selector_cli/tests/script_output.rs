@@ -0,0 +1,48 @@
+// Only Linux reads config path from XDG_CONFIG_HOME, which lets tests use their own config
+#![cfg(target_os = "linux")]
+
+use std::{env, fs, process};
+use std::path::PathBuf;
+use std::process::Command;
+
+fn temp_config_home(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("jdk-selector-cli-test-{}-{}", process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("jdk-selector")).unwrap();
+
+    return dir;
+}
+
+// Runs CLI with given arguments against a config which can't be parsed
+fn run_with_broken_config(name: &str, args: &[&str]) -> process::Output {
+    let config_home = temp_config_home(name);
+    fs::write(config_home.join("jdk-selector").join("config.json"), "{ \"selected_jdk\": ").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_jdk_selector_cli"))
+        .args(args)
+        .env("XDG_CONFIG_HOME", &config_home)
+        .env_remove("JDK_SELECTOR_JDK")
+        .output()
+        .unwrap();
+
+    let _ = fs::remove_dir_all(&config_home);
+    return output;
+}
+
+#[test]
+fn init_export_prints_nothing_to_stdout_on_broken_config() {
+    let output = run_with_broken_config("init-export-broken", &["init", "bash", "--export"]);
+
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+    assert!(!output.stderr.is_empty());
+}
+
+#[test]
+fn shell_prints_nothing_to_stdout_on_broken_config() {
+    let output = run_with_broken_config("shell-broken", &["shell", "--shell", "bash", "17"]);
+
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+    assert!(!output.stderr.is_empty());
+}
@@ -0,0 +1,70 @@
+use std::{env, fs, process};
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use jdk_selector_shared::models::config::Config;
+use jdk_selector_shared::models::jdk_info::JdkInfo;
+use jdk_selector_shared::utils::config_loader::{config, set_config};
+use jdk_selector_shared::utils::resolution_cache::read_cache;
+use jdk_selector_shared::utils::resolver::{resolve_tool, JDK_OVERRIDE_ENV};
+
+const ITERATIONS: u32 = 2000;
+const JDK_COUNT: usize = 40;
+
+// Measures what an executor does before running the tool, with config having many JDKs
+fn main() {
+    let dir = env::temp_dir().join(format!("jdk-selector-bench-{}", process::id()));
+    let config_path = dir.join("config.json");
+    // Project lookup walks up from here, so it has to be outside of any project
+    let current_dir = env::temp_dir();
+    // Cache is bypassed when JDK is overridden by environment variable
+    env::remove_var(JDK_OVERRIDE_ENV);
+
+    let mut sample = Config::default();
+    for index in 0..JDK_COUNT {
+        let mut jdk_info = JdkInfo::new(&format!("temurin-{}", index), &format!("/opt/jdk-{}", index));
+        jdk_info.version = Some(format!("{}.0.{}+7", 8 + index % 14, index));
+        jdk_info.vendor = Some("Eclipse Adoptium".to_string());
+        jdk_info.env.insert("MAVEN_OPTS".to_string(), "-Xmx1g".to_string());
+        sample.jdk_info_list.push(jdk_info);
+    }
+    sample.selected_jdk = Some("17".to_string());
+    sample.tool_overrides.insert("jshell".to_string(), ">=21".to_string());
+
+    if let Err(e) = set_config(&config_path, &sample) {
+        println!("{}", e);
+        process::exit(1);
+    }
+
+    let before = measure(|| {
+        let config = config(&config_path).ok().unwrap();
+        let selection = resolve_tool(&config, &current_dir, "java").ok().unwrap();
+        black_box(Path::new(&selection.jdk_info.path).join("bin").join("java"));
+    });
+    let after = measure(|| {
+        let cache = read_cache(&config_path).unwrap();
+        let selection = cache.resolve("java", &current_dir).unwrap();
+        black_box(Path::new(&selection.jdk_info.path).join("bin").join("java"));
+    });
+
+    println!("Resolution with {} JDKs, average of {} runs", JDK_COUNT, ITERATIONS);
+    println!("\t config.json: {:?}", before);
+    println!("\t cache:       {:?}", after);
+    println!("\t speedup:     {:.1}x", before.as_secs_f64() / after.as_secs_f64());
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+fn measure<F: Fn()>(run: F) -> Duration {
+    // Warm up file system cache first
+    for _ in 0..ITERATIONS / 10 {
+        run();
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        run();
+    }
+
+    return start.elapsed() / ITERATIONS;
+}
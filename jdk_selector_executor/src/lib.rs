@@ -0,0 +1,223 @@
+use std::{env};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+use jdk_selector_shared::{print_on_debug, trace};
+use jdk_selector_shared::utils::config_loader::{config, config_path, file_path};
+use jdk_selector_shared::utils::recursion_guard::{
+    is_executor,
+    recursion_depth,
+    MAX_RECURSION_DEPTH,
+    RECURSION_DEPTH_ENV,
+};
+use jdk_selector_shared::utils::resolution_cache::{config_stamp, read_cache, write_cache};
+use jdk_selector_shared::utils::resolver::{resolve_tool_binary, ResolveError, Selection, ToolResolveError};
+
+// Runs JDK tool named file_name, like java or javac.exe, from JDK resolved for current directory
+pub fn run_executor(file_name: &str, args: Vec<String>) -> ExitCode {
+    print_on_debug!(args.join(" "));
+    print_on_debug!(file_name);
+
+    let _config_path = config_path();
+    let config_path = match &_config_path {
+        Ok(value) => value.config_dir(),
+        Err(e) => {
+            println!("{}", e);
+            return ExitCode::FAILURE;
+        },
+    };
+    print_on_debug!(config_path.display());
+
+    let appended_config_path = config_path.join("config.json");
+    print_on_debug!(appended_config_path.display());
+    trace!("{} is invoked with config file {}", file_name, appended_config_path.display());
+
+    // Project lookup is skipped if current directory has gone
+    let current_dir = env::current_dir().unwrap_or_default();
+    // Overrides are keyed by tool name without .exe
+    let tool = file_name.strip_suffix(env::consts::EXE_SUFFIX).unwrap_or(file_name);
+
+    // Fast path for the common case. Anything unusual like missing tool goes through full resolution
+    let cache = read_cache(&appended_config_path);
+    if let Some(selection) = cache.as_ref().and_then(| value | value.resolve(tool, &current_dir)) {
+        let combined_path = Path::new(&selection.jdk_info.path)
+            .join("bin")
+            .join(file_name);
+
+        if combined_path.is_file() {
+            print_on_debug!(combined_path.display());
+            trace!("Resolution cache is used");
+            let export_java_home = cache.as_ref().is_some_and(| value | value.export_java_home);
+
+            return launch(&selection, combined_path, export_java_home, tool, args);
+        }
+    }
+    match &cache {
+        Some(..) => trace!("Resolution cache doesn't apply, resolving from config file"),
+        None => trace!("Resolution cache is missing or stale, resolving from config file"),
+    };
+
+    // Taken before reading so config saved by CLI meanwhile makes the cache stale instead of wrong
+    let stamp = config_stamp(&appended_config_path);
+    let config = match config(&appended_config_path) {
+        Ok(value) => value,
+        Err(e) => {
+            println!("{}", e);
+            return ExitCode::FAILURE;
+        },
+    };
+    print_on_debug!(&config);
+
+    // Config has been edited by hand or cache has never been written
+    if let (None, Ok(stamp)) = (&cache, &stamp) {
+        let _ = write_cache(&appended_config_path, stamp, &config);
+    }
+
+    let resolution = match resolve_tool_binary(&config, &current_dir, file_name) {
+        Ok(value) => value,
+        Err(e @ ToolResolveError::Resolve(ResolveError::NotSelected)) => {
+            println!("{}", e);
+            println!("Did you forget setting config using jdk_selector_cli?");
+            println!("See 'jdk_selector_cli help' for how to use cli.");
+            return ExitCode::FAILURE;
+        },
+        Err(e @ ToolResolveError::Resolve(..)) => {
+            println!("{}", e);
+            println!("See 'jdk_selector_cli help' for how to use cli.");
+            return ExitCode::FAILURE;
+        },
+        Err(e) => {
+            println!("{}", e);
+            return ExitCode::FAILURE;
+        },
+    };
+    print_on_debug!(resolution.binary.display());
+
+    return launch(&resolution.selection, resolution.binary, config.export_java_home, tool, args);
+}
+
+fn launch(
+    selection: &Selection,
+    combined_path: PathBuf,
+    export_java_home: bool,
+    tool: &str,
+    args: Vec<String>,
+) -> ExitCode {
+    let selected_jdk_info = selection.jdk_info;
+    trace!("{} is selected by {}", selected_jdk_info, selection.source);
+    trace!("Binary: {}", combined_path.display());
+
+    // Tools of selected JDK may run executors again through wrapper scripts, which can't be told by path
+    let depth = recursion_depth();
+    if depth >= MAX_RECURSION_DEPTH {
+        println!(
+            "JDK Selector executors have called each other {} times in a row while running {} selected by {}.",
+            depth,
+            selected_jdk_info,
+            selection.source,
+        );
+        println!("Check whether its tools run JDK Selector's executors again.");
+        return ExitCode::FAILURE;
+    }
+
+    if let Ok(executor_path) = &file_path() {
+        if is_executor(&combined_path, executor_path) {
+            println!(
+                "{} is selected by {} but {} is JDK Selector's executor.",
+                selected_jdk_info,
+                selection.source,
+                combined_path.display(),
+            );
+            println!(
+                "Point it to actual JDK using 'jdk_selector_cli remove {}' and 'jdk_selector_cli add'.",
+                selected_jdk_info.name,
+            );
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let launch_arguments = selected_jdk_info.launch_arguments(tool);
+    trace!("Arguments: {:?}", launch_arguments.iter().chain(args.iter()).collect::<Vec<&String>>());
+    for (key, value) in selected_jdk_info.env.iter() {
+        trace!("Environment: {}={}", key, value);
+    }
+
+    let mut command = Command::new(combined_path);
+    command.args(launch_arguments)
+        .args(args)
+        .envs(env::vars())
+        .envs(&selected_jdk_info.env)
+        .env(RECURSION_DEPTH_ENV, (depth + 1).to_string());
+
+    // Build tools and scripts started by the tool find the same JDK instead of whatever shell had
+    if export_java_home {
+        command.env("JAVA_HOME", &selected_jdk_info.path);
+        trace!("Environment: JAVA_HOME={}", selected_jdk_info.path);
+        // PATH is left as is if JDK path can't be joined to it
+        if let Some(value) = selected_jdk_info.prepended_path() {
+            trace!("Environment: PATH={}", value.to_string_lossy());
+            command.env("PATH", value);
+        }
+    }
+
+    return run_command(command);
+}
+
+// Replaces the executor with the command so PID, signals and exit status are the command's own
+#[cfg(unix)]
+pub fn run_command(mut command: Command) -> ExitCode {
+    use std::os::unix::process::CommandExt;
+
+    let e = command.exec();
+    println!("{}", e);
+    println!("Couldn't execute command successfully. (1)");
+    return ExitCode::FAILURE;
+}
+
+#[cfg(not(unix))]
+pub fn run_command(mut command: Command) -> ExitCode {
+    #[cfg(windows)]
+    ignore_console_interrupts();
+
+    let mut _cmd = command.spawn();
+    let cmd = match &mut _cmd {
+        Ok(value) => value,
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't execute command successfully. (1)");
+            return ExitCode::FAILURE;
+        },
+    };
+
+    match cmd.wait() {
+        // Exit codes on Windows don't fit in u8 so they are passed as-is
+        Ok(status) => match status.code() {
+            Some(code) => std::process::exit(code),
+            None => ExitCode::FAILURE,
+        },
+        Err(e) => {
+            println!("{}", e);
+            println!("Couldn't execute command successfully. (2)");
+            return ExitCode::FAILURE;
+        },
+    }
+}
+
+// Child shares the console and receives Ctrl-C by itself,
+// so executor only has to stay alive until it can report the child's exit code
+#[cfg(windows)]
+fn ignore_console_interrupts() {
+    type HandlerRoutine = unsafe extern "system" fn(u32) -> i32;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn SetConsoleCtrlHandler(handler: Option<HandlerRoutine>, add: i32) -> i32;
+    }
+
+    unsafe extern "system" fn handler(_ctrl_type: u32) -> i32 {
+        1
+    }
+
+    unsafe {
+        SetConsoleCtrlHandler(Some(handler), 1);
+    }
+}
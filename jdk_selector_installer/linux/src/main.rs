@@ -1,4 +1,4 @@
-use std::fs::{copy, create_dir_all, File, OpenOptions, read_dir, remove_dir_all, remove_file};
+use std::fs::{copy, create_dir_all, File, OpenOptions, read_dir, remove_dir_all};
 use std::io::{Error, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command, ExitCode};
@@ -41,19 +41,18 @@ fn build_deb(author: &str, description: &str, version: &str) -> Result<ExitCode,
     let debian_folder_path = &root_folder_path.join("DEBIAN");
 
     let _ = remove_dir_all(&root_folder_path);
-    create_dir_all(&install_folder_path)?;
-    create_dir_all(&debian_folder_path)?;
-    copy_dir_all(Path::new("./.build"), &install_folder_path)?;
-
-    File::create(&debian_folder_path.join("control"))?
-        .write_all(format!(
-            "{}{}{}{}{}",
-            "Package: jdk-selector\n",
+    create_dir_all(install_folder_path)?;
+    create_dir_all(debian_folder_path)?;
+    copy_dir_all(Path::new("./.build"), install_folder_path)?;
+
+    File::create(debian_folder_path.join("control"))?
+        .write_all([
+            "Package: jdk-selector\n".to_string(),
             format!("Version: {}\n", version),
-            "Architecture: amd64\n", // TODO: Read rust output path to determine architecture
+            "Architecture: amd64\n".to_string(), // TODO: Read rust output path to determine architecture
             format!("Maintainer: {}\n", author),
-            format!("Description: {}\n", if description == "" { "N/A" } else { description }),
-        ).as_bytes())?;
+            format!("Description: {}\n", if description.is_empty() { "N/A" } else { description }),
+        ].concat().as_bytes())?;
 
     #[cfg(unix)]
     set_permission_with_path(&root_folder_path)?;
@@ -80,11 +79,11 @@ fn build_deb(author: &str, description: &str, version: &str) -> Result<ExitCode,
     #[cfg(unix)]
     set_file_permission(&prerm, 0o755)?;
 
-    let mut _cmd = Command::new("dpkg-deb").args(&[
+    let mut _cmd = Command::new("dpkg-deb").args([
         "--build",
         "--root-owner-group",
         format!("jdk_selector-{}-noarch", version).as_str(),
-    ]).current_dir(&root_folder_path.join("..")).spawn();
+    ]).current_dir(root_folder_path.join("..")).spawn();
 
     let cmd = match &mut _cmd {
         Ok(value) => value,
@@ -118,7 +117,7 @@ fn build_rpm(description: &str, version: &str) -> Result<ExitCode, Error> {
     let absolute_spec_file_path = &absolute_root_folder_path.join("SPECS")
         .join("jdk-selector.spec");
 
-    copy_dir_all(&Path::new(".build"), &build_root_folder_path)?;
+    copy_dir_all(Path::new(".build"), build_root_folder_path)?;
 
     let mut _raw_script: Vec<StrPtrOrString> = vec![];
     _raw_script.push(StrPtrOrString::StrPtr("%define _binary_payload w3.zstdio"));
@@ -134,7 +133,7 @@ fn build_rpm(description: &str, version: &str) -> Result<ExitCode, Error> {
     _raw_script.push(StrPtrOrString::String(
         format!(
             "Summary: {}",
-            if description == "" { "N/A" } else { description.lines().nth(0).unwrap() },
+            if description.is_empty() { "N/A" } else { description.lines().next().unwrap() },
         ),
     ));
     // TODO: Read rust output path to determine architecture
@@ -145,7 +144,7 @@ fn build_rpm(description: &str, version: &str) -> Result<ExitCode, Error> {
     _raw_script.push(StrPtrOrString::String(
         format!(
             "{}\n",
-            if description == "" { "N/A" } else { description },
+            if description.is_empty() { "N/A" } else { description },
         ),
     ));
 
@@ -177,9 +176,9 @@ fn build_rpm(description: &str, version: &str) -> Result<ExitCode, Error> {
         }
     }).collect();
 
-    File::create(spec_file_path)?.write_all(&raw_script.join("\n").as_bytes())?;
+    File::create(spec_file_path)?.write_all(raw_script.join("\n").as_bytes())?;
 
-    let mut _build_package_cmd = Command::new("rpmbuild").args(&[
+    let mut _build_package_cmd = Command::new("rpmbuild").args([
         "-ba",
         &absolute_spec_file_path.to_string_lossy(),
     ]).current_dir(&root_folder_path).spawn();
@@ -208,11 +207,13 @@ fn copy_dir_all(src: &Path, dist: &Path) -> Result<(), Error> {
         let entry_type = entry.file_type()?;
 
         if entry_type.is_dir() {
-            copy_dir_all(&entry.path(), &dist.join(&entry.file_name()))?;
+            copy_dir_all(&entry.path(), &dist.join(entry.file_name()))?;
         } else if entry_type.is_symlink() {
-            continue;
+            // Executors are links to jdk_selector_cli and have to stay so
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(std::fs::read_link(entry.path())?, dist.join(entry.file_name()))?;
         } else {
-            copy(&entry.path(), &dist.join(&entry.file_name()))?;
+            copy(entry.path(), dist.join(entry.file_name()))?;
         }
     }
 
@@ -228,7 +229,7 @@ fn create_file(path: &Path) -> Result<File, Error> {
 fn create_file(path: &Path) -> Result<File, Error> {
     use std::os::unix::fs::OpenOptionsExt;
 
-    OpenOptions::new().read(true).write(true).create_new(true).mode(0o644).open(&path)
+    OpenOptions::new().read(true).write(true).create_new(true).mode(0o644).open(path)
 }
 
 #[cfg(unix)]
@@ -244,7 +245,7 @@ fn set_permission_with_path(path: &Path) -> Result<(), Error> {
             continue;
         }
 
-        set_permissions(&entry.path(), Permissions::from_mode(0o755))?;
+        set_permissions(entry.path(), Permissions::from_mode(0o755))?;
     }
 
     Ok(())
@@ -7,4 +7,14 @@ macro_rules! print_on_debug {
         #[cfg(debug_assertions)]
         std::dbg!($($rest)*)
     }
-}
\ No newline at end of file
+}
+
+// Prints to stderr only if tracing is enabled by JDK_SELECTOR_TRACE or --trace
+#[macro_export]
+macro_rules! trace {
+    ($($rest:tt)*) => {
+        if $crate::utils::trace::trace_enabled() {
+            std::eprintln!("[jdk-selector] {}", std::format!($($rest)*));
+        }
+    }
+}
@@ -1,25 +1,74 @@
+use std::collections::BTreeMap;
 use std::fmt;
 use serde::{Serialize, Deserialize};
 use crate::models::jdk_info::JdkInfo;
+use crate::utils::config_loader::CONFIG_SCHEMA_VERSION;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Config {
-    pub selected_jdk: Option<usize>,
+    pub schema_version: u64,
+    // Name of selected JDK in jdk_info_list
+    pub selected_jdk: Option<String>,
     pub jdk_info_list: Vec<JdkInfo>,
+    // Directories searched by scan command in addition to well-known locations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scan_roots: Vec<String>,
+    // Vendor aliases preferred when several JDKs match version constraint equally, most preferred first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preferred_vendors: Vec<String>,
+    // Runs tool from newest JDK providing it when selected JDK doesn't have the tool
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub tool_fallback: bool,
+    // Tool name like jshell to JDK name or version constraint always used for that tool
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tool_overrides: BTreeMap<String, String>,
+    // Sets JAVA_HOME and prepends bin of resolved JDK to PATH for tools and their children
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub export_java_home: bool,
 }
 
-impl fmt::Display for Config {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let steps = || -> Result<&JdkInfo, ()> {
-            let index = self.selected_jdk.ok_or(())?;
-            let item = self.jdk_info_list.get(index).ok_or(())?;
-
-            Ok(item)
+impl Default for Config {
+    fn default() -> Config {
+        return Config {
+            schema_version: CONFIG_SCHEMA_VERSION,
+            selected_jdk: None,
+            jdk_info_list: vec![],
+            scan_roots: vec![],
+            preferred_vendors: vec![],
+            tool_fallback: false,
+            tool_overrides: BTreeMap::new(),
+            export_java_home: true,
         };
+    }
+}
+
+impl Config {
+    pub fn jdk_info(&self, name: &str) -> Option<&JdkInfo> {
+        return self.jdk_info_list.iter().find(| value | value.name == name);
+    }
+
+    pub fn selected_jdk_info(&self) -> Option<&JdkInfo> {
+        return self.jdk_info(self.selected_jdk.as_ref()?);
+    }
+}
 
-        let name = match steps() {
-            Ok(value) => value.name.to_string(),
-            Err(..) => String::from("Undefined"),
+fn is_false(value: &bool) -> bool {
+    return !value;
+}
+
+fn is_true(value: &bool) -> bool {
+    return *value;
+}
+
+fn default_true() -> bool {
+    return true;
+}
+
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self.selected_jdk_info() {
+            Some(value) => value.name.to_string(),
+            None => String::from("Undefined"),
         };
 
         write!(f, "Selected JDK name: {} ", name)?;
@@ -29,4 +78,4 @@ impl fmt::Display for Config {
         }
         write!(f, "]")
     }
-}
\ No newline at end of file
+}
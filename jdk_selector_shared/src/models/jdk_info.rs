@@ -1,14 +1,90 @@
-use std::fmt;
+use std::collections::BTreeMap;
+use std::{env, fmt};
+use std::ffi::OsString;
+use std::path::Path;
 use serde::{Serialize, Deserialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct JdkInfo {
     pub name: String,
     pub path: String,
+    // Metadata below is read from JDK itself and may be missing for old JDKs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vendor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub architecture: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub implementation: Option<String>,
+    // Modified time of bin/java in milliseconds when metadata was probed by running it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub probe_mtime: Option<u64>,
+    // Environment variables like MAVEN_OPTS set for tools of this JDK, replacing inherited ones
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    // JVM flags like -Xmx2g passed to tools of this JDK before any given argument
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub java_options: Vec<String>,
+}
+
+impl JdkInfo {
+    pub fn new(name: &str, path: &str) -> JdkInfo {
+        return JdkInfo {
+            name: name.to_string(),
+            path: path.to_string(),
+            version: None,
+            vendor: None,
+            architecture: None,
+            implementation: None,
+            probe_mtime: None,
+            env: BTreeMap::new(),
+            java_options: vec![],
+        };
+    }
+
+    // Arguments to prepend for given tool. Launchers other than java take JVM flags with -J prefix
+    pub fn launch_arguments(&self, tool: &str) -> Vec<String> {
+        if tool == "java" || tool == "javaw" {
+            return self.java_options.clone();
+        }
+
+        return self.java_options.iter()
+            .map(| value | format!("-J{}", value))
+            .collect();
+    }
+
+    // PATH of current process with bin of this JDK prepended. None if JDK path itself contains separator
+    pub fn prepended_path(&self) -> Option<OsString> {
+        let mut paths = vec![Path::new(&self.path).join("bin")];
+        if let Some(value) = env::var_os("PATH") {
+            paths.extend(env::split_paths(&value));
+        }
+
+        return env::join_paths(paths).ok();
+    }
+
+    // Joins available metadata like "17.0.8+7, Eclipse Adoptium, x86_64, Hotspot"
+    pub fn metadata_summary(&self) -> Option<String> {
+        let metadata: Vec<&str> = [&self.version, &self.vendor, &self.architecture, &self.implementation]
+            .iter()
+            .filter_map(| value | value.as_deref())
+            .collect();
+
+        if metadata.is_empty() {
+            return None;
+        }
+
+        return Some(metadata.join(", "));
+    }
 }
 
 impl fmt::Display for JdkInfo {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{{Name: \"{}\", Path: \"{}\"}}", self.name, self.path)
+        write!(f, "{{Name: \"{}\", Path: \"{}\"", self.name, self.path)?;
+        if let Some(metadata) = self.metadata_summary() {
+            write!(f, ", Metadata: \"{}\"", metadata)?;
+        }
+        write!(f, "}}")
     }
-}
\ No newline at end of file
+}
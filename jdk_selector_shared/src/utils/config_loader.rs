@@ -1,12 +1,30 @@
-use std::{env, fs, io};
+use std::{env, fs, io, process};
 use std::fmt::{Display, Formatter};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
 use directories::ProjectDirs;
+use serde_json::Value;
 use crate::models::config::Config;
+use crate::utils::resolution_cache::{config_stamp, write_cache};
+
+// Bump this and append a step to MIGRATIONS only when existing configs have to be converted.
+// Fields added with serde defaults are read fine from older configs, so they don't need a new version
+pub const CONFIG_SCHEMA_VERSION: u64 = 2;
+
+// Step at index N upgrades config from schema version N + 1 to N + 2
+const MIGRATIONS: [fn(&mut Value); (CONFIG_SCHEMA_VERSION - 1) as usize] = [
+    migrate_selected_jdk_index,
+];
 
 pub enum JsonErrorOrIOOrInvalidPathError {
     JsonError(serde_json::Error),
     IOError(io::Error),
+    UnsupportedSchemaVersion(u64),
+    // Parsing error and where the invalid config has been moved to if it could be moved
+    InvalidConfig(serde_json::Error, Option<PathBuf>),
 }
 
 impl Display for JsonErrorOrIOOrInvalidPathError {
@@ -18,6 +36,25 @@ impl Display for JsonErrorOrIOOrInvalidPathError {
             JsonErrorOrIOOrInvalidPathError::IOError(value) => {
                 value.fmt(f)
             }
+            JsonErrorOrIOOrInvalidPathError::UnsupportedSchemaVersion(value) => {
+                write!(
+                    f,
+                    "Config schema version {} is newer than supported version {}. Please update JDK Selector.",
+                    value,
+                    CONFIG_SCHEMA_VERSION,
+                )
+            }
+            JsonErrorOrIOOrInvalidPathError::InvalidConfig(value, quarantine_path) => {
+                write!(f, "Config file is invalid: {}", value)?;
+                match quarantine_path {
+                    Some(path) => write!(
+                        f,
+                        "\nIt has been moved to {}\nRun 'jdk_selector_cli config repair' to recover JDK information from it.",
+                        path.display(),
+                    ),
+                    None => write!(f, "\nFix or remove the config file manually."),
+                }
+            }
         }
     }
 }
@@ -26,6 +63,14 @@ pub fn file_path() -> io::Result<PathBuf> {
     return env::current_exe()?.canonicalize();
 }
 
+// Name this process is invoked by, like java or java.exe, which differs from file_path for links.
+// Unix keeps name of symbolic link in argv[0] only, while Windows links are hard links
+pub fn invoked_file_name() -> Option<String> {
+    let path = if cfg!(windows) { env::current_exe().ok()? } else { PathBuf::from(env::args_os().next()?) };
+
+    return path.file_name().map(| value | value.to_string_lossy().to_string());
+}
+
 pub fn config_path() -> Result<ProjectDirs, &'static str> {
     let project_dir = ProjectDirs::from(
         "com",
@@ -69,14 +114,128 @@ pub fn set_config(path: &Path, config: &Config) -> Result<(), JsonErrorOrIOOrInv
         }
     };
 
-    return match fs::write(path, data) {
-        Ok(..) => Ok(()),
+    return match write_atomically(path, data.as_bytes()) {
+        Ok(..) => {
+            // Cache is only a shortcut, so executors just take slow path if it couldn't be written.
+            // Config saved here is the one just written since every save holds the lock
+            if let Ok(stamp) = config_stamp(path) {
+                let _ = write_cache(path, &stamp, config);
+            }
+            Ok(())
+        },
         Err(e) => {
             return Err(JsonErrorOrIOOrInvalidPathError::IOError(e));
         }
     }
 }
 
+// Writes into temporary file first so config is either old or new one even if process dies in the middle
+fn write_atomically(path: &Path, data: &[u8]) -> io::Result<()> {
+    let temp_path = path.with_extension(format!("json.tmp-{}", process::id()));
+
+    let steps = || -> io::Result<()> {
+        let mut file = File::create(&temp_path)?;
+        file.write_all(data)?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)?;
+
+        // Directory entry has to be flushed as well to persist rename on Unix
+        #[cfg(unix)]
+        if let Some(parent) = path.parent() {
+            File::open(parent)?.sync_all()?;
+        }
+
+        Ok(())
+    };
+
+    let result = steps();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    return result;
+}
+
+// Whether this process holds ConfigLock. Locking again from the same process would wait for itself
+static LOCK_HELD: AtomicBool = AtomicBool::new(false);
+
+// Advisory lock on config.json.lock, released when dropped
+pub struct ConfigLock {
+    _file: File,
+}
+
+impl Drop for ConfigLock {
+    fn drop(&mut self) {
+        LOCK_HELD.store(false, Ordering::SeqCst);
+    }
+}
+
+fn open_lock_file(path: &Path) -> io::Result<File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    return OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path.with_extension("json.lock"));
+}
+
+// Hold returned lock during whole load, modify and save cycle so concurrent CLI calls don't lose updates
+pub fn lock_config(path: &Path) -> Result<ConfigLock, JsonErrorOrIOOrInvalidPathError> {
+    let steps = || -> io::Result<ConfigLock> {
+        let file = open_lock_file(path)?;
+
+        match file.try_lock() {
+            Ok(..) => {},
+            Err(TryLockError::WouldBlock) => {
+                eprintln!("Waiting for another jdk_selector_cli to finish...");
+                file.lock()?;
+            },
+            Err(TryLockError::Error(e)) => return Err(e),
+        };
+
+        LOCK_HELD.store(true, Ordering::SeqCst);
+        Ok(ConfigLock { _file: file })
+    };
+
+    return match steps() {
+        Ok(value) => Ok(value),
+        Err(e) => Err(JsonErrorOrIOOrInvalidPathError::IOError(e)),
+    };
+}
+
+// Saves config which config() has created or migrated on its own, for callers without the lock like executors.
+// Executors don't wait for the lock, so config stays migrated in memory only while another process holds it.
+// unchanged tells whether config file is still what was loaded, since it may have been saved before locking
+fn save_loaded_config<F>(path: &Path, config: &Config, unchanged: F) -> Result<(), JsonErrorOrIOOrInvalidPathError>
+where
+    F: Fn() -> bool,
+{
+    if LOCK_HELD.load(Ordering::SeqCst) {
+        return set_config(path, config);
+    }
+
+    let file = match open_lock_file(path) {
+        Ok(value) => value,
+        Err(e) => return Err(JsonErrorOrIOOrInvalidPathError::IOError(e)),
+    };
+    match file.try_lock() {
+        Ok(..) => {},
+        Err(TryLockError::WouldBlock) => return Ok(()),
+        Err(TryLockError::Error(e)) => return Err(JsonErrorOrIOOrInvalidPathError::IOError(e)),
+    };
+
+    if !unchanged() {
+        return Ok(());
+    }
+
+    // Lock is released when file is closed
+    return set_config(path, config);
+}
+
 pub fn create_config(path: &Path) -> Result<Config, JsonErrorOrIOOrInvalidPathError> {
     let raw_data = r#"
     {
@@ -85,14 +244,22 @@ pub fn create_config(path: &Path) -> Result<Config, JsonErrorOrIOOrInvalidPathEr
     }
     "#;
 
-    let data: Config = match serde_json::from_str(raw_data) {
+    let mut value: Value = match serde_json::from_str(raw_data) {
+        Ok(value) => value,
+        Err(e) => {
+            return Err(JsonErrorOrIOOrInvalidPathError::JsonError(e));
+        }
+    };
+    value["schema_version"] = Value::from(CONFIG_SCHEMA_VERSION);
+
+    let data: Config = match serde_json::from_value(value) {
         Ok(value) => value,
         Err(e) => {
             return Err(JsonErrorOrIOOrInvalidPathError::JsonError(e));
         }
     };
 
-    return match set_config(path, &data) {
+    return match save_loaded_config(path, &data, || !path.exists()) {
         Ok(..) => Ok(data),
         Err(e) => {
             return Err(e);
@@ -101,33 +268,238 @@ pub fn create_config(path: &Path) -> Result<Config, JsonErrorOrIOOrInvalidPathEr
 }
 
 pub fn config(path: &Path) -> Result<Config, JsonErrorOrIOOrInvalidPathError> {
-    let steps = || -> Result<Config, JsonErrorOrIOOrInvalidPathError> {
-        let raw = raw_config(path)?;
+    let raw = match raw_config(path) {
+        Ok(value) => value,
+        Err(JsonErrorOrIOOrInvalidPathError::IOError(e)) if e.kind() == io::ErrorKind::NotFound => {
+            // Notices go to stderr so they don't mix into output of JDK tools or evaluated shell snippets
+            eprintln!("Couldn't find config file.");
+            eprintln!("Creating config...");
 
-        let data: Config = match serde_json::from_str(&raw) {
-            Ok(value) => value,
-            Err(e) => {
-                return Err(JsonErrorOrIOOrInvalidPathError::JsonError(e));
-            }
+            return match create_config(path) {
+                Ok(value) => Ok(value),
+                Err(e) => {
+                    eprintln!("Couldn't create config file.");
+                    return Err(e);
+                }
+            };
+        }
+        // Any other failure must not lead to replacing user's config
+        Err(e) => return Err(e),
+    };
+
+    let mut value: Value = match serde_json::from_str(&raw) {
+        Ok(value) => value,
+        Err(e) => return Err(quarantine_config(path, e)),
+    };
+    let migrated = migrate(path, &raw, &mut value)?;
+
+    // Parsing raw string again keeps line and column in error messages
+    let parsed = if migrated { serde_json::from_value(value) } else { serde_json::from_str(&raw) };
+    let data: Config = match parsed {
+        Ok(value) => value,
+        Err(e) => return Err(quarantine_config(path, e)),
+    };
+
+    if migrated {
+        save_loaded_config(path, &data, || fs::read_to_string(path).is_ok_and(| value | value == raw))?;
+    }
+
+    return Ok(data);
+}
+
+// Returns quarantined config files next to given config path, oldest first
+pub fn quarantined_config_paths(path: &Path) -> io::Result<Vec<PathBuf>> {
+    let file_name = match path.file_name() {
+        Some(value) => format!("{}.corrupt-", value.to_string_lossy()),
+        None => return Ok(vec![]),
+    };
+    let dir = match path.parent() {
+        Some(value) => value,
+        None => return Ok(vec![]),
+    };
+
+    let mut paths: Vec<PathBuf> = vec![];
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_name().to_string_lossy().starts_with(&file_name) {
+            paths.push(entry.path());
+        }
+    }
+    paths.sort();
+
+    return Ok(paths);
+}
+
+fn quarantine_config(path: &Path, error: serde_json::Error) -> JsonErrorOrIOOrInvalidPathError {
+    return match move_to_quarantine(path) {
+        Ok(value) => JsonErrorOrIOOrInvalidPathError::InvalidConfig(error, Some(value)),
+        Err(..) => JsonErrorOrIOOrInvalidPathError::InvalidConfig(error, None),
+    };
+}
+
+// Earlier quarantined configs may still be needed by repair, so a free name is reserved with create_new first.
+// Renaming then replaces only the empty file reserved here
+fn move_to_quarantine(path: &Path) -> io::Result<PathBuf> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let base = format!("json.corrupt-{}.{:09}-{}", timestamp.as_secs(), timestamp.subsec_nanos(), process::id());
+
+    let mut number = 1;
+    let quarantine_path = loop {
+        let candidate = match number {
+            1 => path.with_extension(&base),
+            _ => path.with_extension(format!("{}-{}", base, number)),
+        };
+        match OpenOptions::new().write(true).create_new(true).open(&candidate) {
+            Ok(..) => break candidate,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => number += 1,
+            Err(e) => return Err(e),
         };
+    };
 
-        Ok(data)
+    return match fs::rename(path, &quarantine_path) {
+        Ok(..) => Ok(quarantine_path),
+        Err(e) => {
+            let _ = fs::remove_file(&quarantine_path);
+            Err(e)
+        },
     };
+}
 
-    return match steps() {
-        Ok(value1) => Ok(value1),
-        Err(e1) => {
-            println!("{}", e1);
-            println!("Couldn't retrieve config file.");
-            println!("Creating config...");
-
-            match create_config(path) {
-                Ok(value2) => Ok(value2),
-                Err(e2) => {
-                    println!("Couldn't create config file.");
-                    return Err(e2);
-                }
-            }
+fn migrate(path: &Path, raw: &str, value: &mut Value) -> Result<bool, JsonErrorOrIOOrInvalidPathError> {
+    // Valid JSON other than object, like [] or 42, can't be migrated so it's invalid as any unparsable config
+    if !value.is_object() {
+        return match serde_json::from_value::<Config>(value.clone()) {
+            Ok(..) => Ok(false),
+            Err(e) => Err(quarantine_config(path, e)),
+        };
+    }
+
+    let version = schema_version(value);
+
+    if version > CONFIG_SCHEMA_VERSION {
+        return Err(JsonErrorOrIOOrInvalidPathError::UnsupportedSchemaVersion(version));
+    }
+
+    if version == CONFIG_SCHEMA_VERSION {
+        return Ok(false);
+    }
+
+    let backup_path = path.with_extension(format!("json.v{}.bak", version));
+    match fs::write(&backup_path, raw) {
+        Ok(..) => {}
+        Err(e) => {
+            return Err(JsonErrorOrIOOrInvalidPathError::IOError(e));
         }
     };
-}
\ No newline at end of file
+    eprintln!(
+        "Migrating config from schema version {} to {}. Previous config is saved to {}",
+        version,
+        CONFIG_SCHEMA_VERSION,
+        backup_path.display(),
+    );
+
+    apply_migrations(value, version);
+
+    return Ok(true);
+}
+
+pub(crate) fn schema_version(value: &Value) -> u64 {
+    // Configs created before schema_version was introduced are version 1
+    return value.get("schema_version")
+        .and_then(| v | v.as_u64())
+        .unwrap_or(1);
+}
+
+pub(crate) fn apply_migrations(value: &mut Value, version: u64) {
+    for step in MIGRATIONS.iter().skip((version.max(1) - 1) as usize) {
+        step(value);
+    }
+    value["schema_version"] = Value::from(CONFIG_SCHEMA_VERSION);
+}
+
+// 1 -> 2: Older configs stored selected JDK as index of jdk_info_list, which breaks once list is modified
+fn migrate_selected_jdk_index(value: &mut Value) {
+    let index = match value.get("selected_jdk").and_then(| v | v.as_u64()) {
+        Some(value) => value as usize,
+        None => return,
+    };
+
+    let name = value.get("jdk_info_list")
+        .and_then(| v | v.get(index))
+        .and_then(| v | v.get("name"))
+        .cloned()
+        .unwrap_or(Value::Null);
+    value["selected_jdk"] = name;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_config_path(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("jdk-selector-test-{}-{}", process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        return dir.join("config.json");
+    }
+
+    #[test]
+    fn non_object_config_is_quarantined_without_migrating() {
+        for (index, raw) in ["[]", "42", "\"x\""].iter().enumerate() {
+            let path = temp_config_path(&format!("non-object-{}", index));
+            fs::write(&path, raw).unwrap();
+
+            match config(&path) {
+                Err(JsonErrorOrIOOrInvalidPathError::InvalidConfig(_, Some(quarantine_path))) => {
+                    assert_eq!(fs::read_to_string(quarantine_path).unwrap(), *raw);
+                },
+                _ => panic!("{} should be quarantined as invalid config", raw),
+            };
+            assert!(!path.exists());
+            assert!(!path.with_extension("json.v1.bak").exists());
+
+            fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        }
+    }
+
+    #[test]
+    fn quarantine_keeps_earlier_quarantined_configs() {
+        let path = temp_config_path("quarantine-twice");
+
+        for raw in ["{ first", "{ second"] {
+            fs::write(&path, raw).unwrap();
+            assert!(matches!(config(&path), Err(JsonErrorOrIOOrInvalidPathError::InvalidConfig(_, Some(..)))));
+        }
+
+        let contents: Vec<String> = quarantined_config_paths(&path).unwrap().iter()
+            .map(| value | fs::read_to_string(value).unwrap())
+            .collect();
+        assert_eq!(contents, ["{ first", "{ second"]);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn migration_is_saved_only_without_concurrent_lock() {
+        let path = temp_config_path("migration-lock");
+        let raw = r#"{"selected_jdk": null, "jdk_info_list": []}"#;
+        fs::write(&path, raw).unwrap();
+
+        // Another process modifying config holds the lock
+        let lock = open_lock_file(&path).unwrap();
+        lock.lock().unwrap();
+        let loaded = config(&path).ok().unwrap();
+        assert_eq!(loaded.schema_version, CONFIG_SCHEMA_VERSION);
+        assert_eq!(fs::read_to_string(&path).unwrap(), raw);
+
+        drop(lock);
+        config(&path).ok().unwrap();
+        let saved: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(schema_version(&saved), CONFIG_SCHEMA_VERSION);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
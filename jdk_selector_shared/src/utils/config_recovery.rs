@@ -0,0 +1,202 @@
+use serde_json::Value;
+use crate::models::config::Config;
+use crate::models::jdk_info::JdkInfo;
+use crate::utils::config_loader::{apply_migrations, schema_version, CONFIG_SCHEMA_VERSION};
+
+// Recovers as much as possible from config which couldn't be parsed
+pub fn recover_config(raw: &str) -> Config {
+    let value = serde_json::from_str::<Value>(raw)
+        .or_else(| _ | serde_json::from_str::<Value>(&remove_trailing_commas(raw)));
+
+    return match value {
+        Ok(value) if value.is_object() => recover_from_value(value),
+        // Valid JSON other than object, like [] or 42, can't be migrated but may still have JDK information in it
+        _ => scan_config(raw),
+    };
+}
+
+fn recover_from_value(mut value: Value) -> Config {
+    let version = schema_version(&value);
+    if version <= CONFIG_SCHEMA_VERSION {
+        apply_migrations(&mut value, version);
+    }
+
+    // Broken entries are skipped one by one instead of discarding whole list
+    let jdk_info_list = value.get("jdk_info_list")
+        .and_then(| v | v.as_array())
+        .map(| list | {
+            list.iter()
+                .filter_map(| item | serde_json::from_value::<JdkInfo>(item.clone()).ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    let selected_jdk = value.get("selected_jdk")
+        .and_then(| v | v.as_str())
+        .map(| v | v.to_string());
+
+    return Config {
+        selected_jdk,
+        jdk_info_list,
+        ..Config::default()
+    };
+}
+
+fn remove_trailing_commas(raw: &str) -> String {
+    let chars: Vec<char> = raw.chars().collect();
+    let mut result = String::with_capacity(raw.len());
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (index, char) in chars.iter().enumerate() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if *char == '\\' {
+                escaped = true;
+            } else if *char == '"' {
+                in_string = false;
+            }
+        } else if *char == '"' {
+            in_string = true;
+        } else if *char == ',' {
+            let next = chars.iter().skip(index + 1).find(| value | !value.is_whitespace());
+            if matches!(next, Some('}') | Some(']')) {
+                continue;
+            }
+        }
+
+        result.push(*char);
+    }
+
+    return result;
+}
+
+// Last resort for configs that aren't JSON anymore. Picks up objects having both name and path
+fn scan_config(raw: &str) -> Config {
+    let chars: Vec<char> = raw.chars().collect();
+    let mut selected_jdk: Option<String> = None;
+    let mut list: Vec<JdkInfo> = vec![];
+    let mut objects: Vec<(Option<String>, Option<String>)> = vec![];
+    let mut last_string: Option<String> = None;
+    let mut key: Option<String> = None;
+    let mut index = 0;
+
+    while index < chars.len() {
+        match chars[index] {
+            '{' => {
+                objects.push((None, None));
+                last_string = None;
+                key = None;
+            },
+            '}' => {
+                if let Some((Some(name), Some(path))) = objects.pop() {
+                    list.push(JdkInfo::new(&name, &path));
+                }
+                key = None;
+            },
+            ':' => {
+                key = last_string.take();
+            },
+            ',' => {
+                last_string = None;
+                key = None;
+            },
+            '"' => {
+                let (value, next_index) = read_string(&chars, index);
+                index = next_index;
+
+                let depth = objects.len();
+                match (key.take().as_deref(), objects.last_mut()) {
+                    (Some("selected_jdk"), Some(..)) if depth == 1 => selected_jdk = value,
+                    (Some("name"), Some(object)) => object.0 = value,
+                    (Some("path"), Some(object)) => object.1 = value,
+                    (Some(..), _) => {},
+                    (None, _) => last_string = value,
+                }
+            },
+            _ => {},
+        }
+
+        index += 1;
+    }
+
+    return Config {
+        selected_jdk,
+        jdk_info_list: list,
+        ..Config::default()
+    };
+}
+
+// Returns unescaped string starting at given quote and index of closing quote
+fn read_string(chars: &[char], start: usize) -> (Option<String>, usize) {
+    let mut escaped = false;
+
+    for (index, char) in chars.iter().enumerate().skip(start + 1) {
+        if escaped {
+            escaped = false;
+        } else if *char == '\\' {
+            escaped = true;
+        } else if *char == '"' {
+            let raw: String = chars[start..=index].iter().collect();
+
+            return (serde_json::from_str(&raw).ok(), index);
+        }
+    }
+
+    return (None, chars.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_object_config_is_salvaged() {
+        for raw in ["[]", "42", "\"x\""] {
+            let config = recover_config(raw);
+            assert!(config.selected_jdk.is_none());
+            assert!(config.jdk_info_list.is_empty());
+        }
+
+        let config = recover_config(r#"[{"name": "17", "path": "/opt/jdk-17"}]"#);
+        assert_eq!(config.jdk_info_list.len(), 1);
+        assert_eq!(config.jdk_info_list[0].path, "/opt/jdk-17");
+    }
+
+    fn names(config: &Config) -> Vec<&str> {
+        return config.jdk_info_list.iter().map(| value | value.name.as_str()).collect();
+    }
+
+    #[test]
+    fn trailing_commas_are_ignored() {
+        let raw = r#"{
+            "schema_version": 2,
+            "selected_jdk": "17",
+            "jdk_info_list": [
+                {"name": "11", "path": "/opt/jdk-11",},
+                {"name": "broken"},
+                {"name": "17", "path": "/opt/jdk-17, not a comma to remove",},
+            ],
+        }"#;
+
+        let config = recover_config(raw);
+        assert_eq!(config.selected_jdk.as_deref(), Some("17"));
+        // Entry without path can't be used, but doesn't take others with it
+        assert_eq!(names(&config), ["11", "17"]);
+        assert_eq!(config.jdk_info_list[1].path, "/opt/jdk-17, not a comma to remove");
+    }
+
+    #[test]
+    fn truncated_config_keeps_complete_entries() {
+        let raw = r#"{
+            "selected_jdk": "11",
+            "jdk_info_list": [
+                {"name": "11", "path": "/opt/jdk-11"},
+                {"name": "17", "path": "/opt/jdk-17"},
+                {"name": "21", "path": "/opt/j"#;
+
+        let config = recover_config(raw);
+        assert_eq!(config.selected_jdk.as_deref(), Some("11"));
+        assert_eq!(names(&config), ["11", "17"]);
+    }
+}
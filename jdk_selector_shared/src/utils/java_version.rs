@@ -0,0 +1,181 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+// Java version like 1.8.0_321-b07, 17.0.8+7 or 21-ea in comparable form
+// Missing numbers count as 0, so 17 and 17.0.0 are equal both by == and by cmp
+#[derive(Debug, Clone)]
+pub struct JavaVersion {
+    // Feature, interim, update and patch numbers. Legacy 1.x versions start from x
+    pub numbers: Vec<u64>,
+    // Pre-release identifier like ea, which comes before release of same numbers
+    pub pre: Option<String>,
+    pub build: Option<u64>,
+}
+
+impl JavaVersion {
+    pub fn parse(raw: &str) -> Option<JavaVersion> {
+        let raw = raw.trim();
+        let (main, build) = match raw.split_once('+') {
+            Some((main, build)) => (main, leading_number(build)),
+            None => (raw, None),
+        };
+
+        let (numbers, suffix) = match main.split_once('-') {
+            Some((numbers, suffix)) => (numbers, Some(suffix)),
+            None => (main, None),
+        };
+
+        // Legacy versions use -b07 suffix for build number instead of +7
+        let (pre, build) = match suffix {
+            Some(value) if value.starts_with('b') && leading_number(&value[1..]).is_some() => {
+                (None, build.or(leading_number(&value[1..])))
+            },
+            Some(value) if !value.is_empty() => (Some(value.to_string()), build),
+            _ => (None, build),
+        };
+
+        // 1.8.0_321 has update number after underscore
+        let mut parsed: Vec<u64> = vec![];
+        for part in numbers.split(['.', '_']) {
+            parsed.push(part.parse().ok()?);
+        }
+
+        if parsed.is_empty() {
+            return None;
+        }
+
+        if parsed.len() > 1 && parsed[0] == 1 {
+            parsed.remove(0);
+        }
+
+        return Some(JavaVersion {
+            numbers: parsed,
+            pre,
+            build,
+        });
+    }
+
+    // Compares only as many numbers as other has, so 17.0.8 equals 17 and 17.0
+    pub fn cmp_prefix(&self, other: &JavaVersion) -> Ordering {
+        for (index, number) in other.numbers.iter().enumerate() {
+            let ordering = self.numbers.get(index).unwrap_or(&0).cmp(number);
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+
+        return Ordering::Equal;
+    }
+}
+
+impl Ord for JavaVersion {
+    fn cmp(&self, other: &JavaVersion) -> Ordering {
+        let length = self.numbers.len().max(other.numbers.len());
+        for index in 0..length {
+            let ordering = self.numbers.get(index).unwrap_or(&0)
+                .cmp(other.numbers.get(index).unwrap_or(&0));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+
+        let pre_ordering = match (&self.pre, &other.pre) {
+            (None, None) => Ordering::Equal,
+            (None, Some(..)) => Ordering::Greater,
+            (Some(..), None) => Ordering::Less,
+            (Some(a), Some(b)) => a.cmp(b),
+        };
+        if pre_ordering != Ordering::Equal {
+            return pre_ordering;
+        }
+
+        return self.build.unwrap_or(0).cmp(&other.build.unwrap_or(0));
+    }
+}
+
+impl PartialEq for JavaVersion {
+    fn eq(&self, other: &JavaVersion) -> bool {
+        return self.cmp(other) == Ordering::Equal;
+    }
+}
+
+impl Eq for JavaVersion {}
+
+impl PartialOrd for JavaVersion {
+    fn partial_cmp(&self, other: &JavaVersion) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl Display for JavaVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let numbers: Vec<String> = self.numbers.iter().map(| value | value.to_string()).collect();
+        write!(f, "{}", numbers.join("."))?;
+        if let Some(pre) = &self.pre {
+            write!(f, "-{}", pre)?;
+        }
+        if let Some(build) = &self.build {
+            write!(f, "+{}", build)?;
+        }
+
+        return Ok(());
+    }
+}
+
+fn leading_number(raw: &str) -> Option<u64> {
+    let digits: String = raw.chars().take_while(| value | value.is_ascii_digit()).collect();
+
+    return digits.parse().ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(raw: &str) -> JavaVersion {
+        return JavaVersion::parse(raw).unwrap();
+    }
+
+    #[test]
+    fn parses_legacy_and_modern_versions() {
+        let legacy = version("1.8.0_321");
+        assert_eq!(legacy.numbers, vec![8, 0, 321]);
+        assert_eq!(legacy.pre, None);
+
+        let legacy_build = version("1.8.0_321-b07");
+        assert_eq!(legacy_build.numbers, vec![8, 0, 321]);
+        assert_eq!(legacy_build.build, Some(7));
+
+        let modern = version("17.0.8+7");
+        assert_eq!(modern.numbers, vec![17, 0, 8]);
+        assert_eq!(modern.build, Some(7));
+
+        let early_access = version("21-ea");
+        assert_eq!(early_access.numbers, vec![21]);
+        assert_eq!(early_access.pre.as_deref(), Some("ea"));
+
+        assert!(JavaVersion::parse("abc").is_none());
+    }
+
+    #[test]
+    fn orders_versions() {
+        assert!(version("1.8.0_321") < version("11"));
+        assert!(version("11") < version("11.0.2"));
+        assert!(version("17.0.8+7") < version("17.0.8+8"));
+        assert!(version("17.0.8+7") < version("21-ea"));
+        assert!(version("21-ea") < version("21"));
+
+        let mut versions = [version("21"), version("1.8.0_321"), version("21-ea"), version("17.0.8+7")];
+        versions.sort();
+        let sorted: Vec<String> = versions.iter().map(| value | value.to_string()).collect();
+        assert_eq!(sorted, ["8.0.321", "17.0.8+7", "21-ea", "21"]);
+    }
+
+    #[test]
+    fn equality_agrees_with_ordering() {
+        assert_eq!(version("17"), version("17.0.0"));
+        assert_eq!(version("17").cmp(&version("17.0.0")), Ordering::Equal);
+        assert_ne!(version("11"), version("11.0.2"));
+        assert_ne!(version("21-ea"), version("21"));
+    }
+}
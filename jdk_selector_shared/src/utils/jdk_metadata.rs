@@ -0,0 +1,111 @@
+use std::{fs, io};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+use crate::models::jdk_info::JdkInfo;
+use crate::utils::jvm_prober::{java_binary_path, probe_jvm};
+use crate::utils::release_file::read_release_file;
+
+pub enum MetadataSource {
+    ReleaseFile,
+    Probe,
+    // Probe result in config is still valid since java binary hasn't changed
+    ProbeCache,
+}
+
+impl Display for MetadataSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            MetadataSource::ReleaseFile => write!(f, "release file"),
+            MetadataSource::Probe => write!(f, "running java"),
+            MetadataSource::ProbeCache => write!(f, "cached result of running java"),
+        }
+    }
+}
+
+// Fills version, vendor, architecture and implementation of JdkInfo from its release file,
+// or from system properties of its JVM if release file isn't available or lacks version
+pub fn refresh_metadata(jdk_info: &mut JdkInfo) -> io::Result<MetadataSource> {
+    let jdk_path = Path::new(&jdk_info.path);
+
+    let release_error = match read_release_file(jdk_path) {
+        // Version is what selection relies on most, so release file without it is as good as missing
+        Ok(release_info) if release_info.java_version.is_some() || release_info.java_runtime_version.is_some() => {
+            // Runtime version carries build number which tells apart JDKs with same feature version
+            jdk_info.version = release_info.java_runtime_version.or(release_info.java_version);
+            jdk_info.vendor = release_info.implementor;
+            jdk_info.architecture = release_info.os_arch;
+            jdk_info.implementation = release_info.jvm_variant;
+            jdk_info.probe_mtime = None;
+
+            return Ok(MetadataSource::ReleaseFile);
+        },
+        Ok(..) => io::Error::new(io::ErrorKind::InvalidData, "release file doesn't have JAVA_VERSION"),
+        Err(e) => e,
+    };
+
+    let java_mtime = match java_mtime(jdk_path) {
+        Ok(value) => value,
+        // Neither release file nor java exists
+        Err(..) => return Err(release_error),
+    };
+    if jdk_info.probe_mtime == Some(java_mtime) {
+        return Ok(MetadataSource::ProbeCache);
+    }
+
+    let properties = probe_jvm(jdk_path)?;
+    jdk_info.version = properties.java_runtime_version.or(properties.java_version);
+    jdk_info.vendor = properties.java_vendor;
+    jdk_info.architecture = properties.os_arch;
+    jdk_info.implementation = properties.java_vm_name;
+    jdk_info.probe_mtime = Some(java_mtime);
+
+    return Ok(MetadataSource::Probe);
+}
+
+fn java_mtime(jdk_path: &Path) -> io::Result<u64> {
+    let modified = fs::metadata(java_binary_path(jdk_path))?.modified()?;
+
+    return match modified.duration_since(UNIX_EPOCH) {
+        Ok(value) => Ok(value.as_millis() as u64),
+        Err(..) => Ok(0),
+    };
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{env, process};
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::PathBuf;
+    use super::*;
+
+    // JDK whose java prints given system properties like -XshowSettings:properties does
+    fn stub_jdk(name: &str, release: &str, properties: &str) -> PathBuf {
+        let jdk_path = env::temp_dir().join(format!("jdk-selector-test-{}-{}", process::id(), name));
+        let _ = fs::remove_dir_all(&jdk_path);
+        fs::create_dir_all(jdk_path.join("bin")).unwrap();
+        fs::write(jdk_path.join("release"), release).unwrap();
+
+        let java_path = java_binary_path(&jdk_path);
+        fs::write(&java_path, format!("#!/bin/sh\ncat >&2 <<'EOF'\n{}EOF\n", properties)).unwrap();
+        fs::set_permissions(&java_path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        return jdk_path;
+    }
+
+    #[test]
+    fn release_file_without_version_falls_back_to_probing() {
+        let jdk_path = stub_jdk(
+            "release-without-version",
+            "IMPLEMENTOR=\"Azul Systems, Inc.\"\nOS_ARCH=\"x86_64\"\n",
+            "Property settings:\n    java.runtime.version = 1.8.0_392-b08\n    java.version = 1.8.0_392\n",
+        );
+        let mut jdk_info = JdkInfo::new("8", &jdk_path.to_string_lossy());
+
+        assert!(matches!(refresh_metadata(&mut jdk_info), Ok(MetadataSource::Probe)));
+        assert_eq!(jdk_info.version.as_deref(), Some("1.8.0_392-b08"));
+        assert!(jdk_info.probe_mtime.is_some());
+
+        fs::remove_dir_all(&jdk_path).unwrap();
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use directories::BaseDirs;
+
+// Common places where package managers, version managers and IDEs install JDKs
+pub fn default_scan_roots() -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = vec![];
+
+    if cfg!(target_os = "linux") {
+        roots.push(PathBuf::from("/usr/lib/jvm"));
+        roots.push(PathBuf::from("/opt"));
+    }
+
+    if cfg!(target_os = "macos") {
+        roots.push(PathBuf::from("/Library/Java/JavaVirtualMachines"));
+    }
+
+    if cfg!(windows) {
+        if let Some(program_files) = std::env::var_os("ProgramFiles") {
+            let program_files = PathBuf::from(program_files);
+            for vendor in ["Java", "Eclipse Adoptium", "Zulu", "Amazon Corretto", "Microsoft", "BellSoft"] {
+                roots.push(program_files.join(vendor));
+            }
+        }
+    }
+
+    if let Some(base_dirs) = BaseDirs::new() {
+        let home_dir = base_dirs.home_dir();
+        roots.push(home_dir.join(".sdkman/candidates/java"));
+        roots.push(home_dir.join(".jdks"));
+        roots.push(home_dir.join(".gradle/jdks"));
+        roots.push(home_dir.join(".asdf/installs/java"));
+    }
+
+    return roots;
+}
+
+// Returns canonical paths of JDK homes found in roots and up to two levels below them
+pub fn scan_jdk_homes(roots: &[PathBuf]) -> Vec<PathBuf> {
+    let mut found: Vec<PathBuf> = vec![];
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+
+    for root in roots {
+        scan_dir(root, 2, &mut found, &mut visited);
+    }
+
+    return found;
+}
+
+pub fn is_jdk_home(path: &Path) -> bool {
+    let java = path.join("bin").join(format!("java{}", std::env::consts::EXE_SUFFIX));
+
+    return java.is_file() && path.join("release").is_file();
+}
+
+fn scan_dir(dir: &Path, depth: usize, found: &mut Vec<PathBuf>, visited: &mut HashSet<PathBuf>) {
+    // Symlinks like sdkman's current point to other candidates, so canonical path is compared
+    let canonical_dir = match dir.canonicalize() {
+        Ok(value) => value,
+        Err(..) => return,
+    };
+    if !visited.insert(canonical_dir.clone()) {
+        return;
+    }
+
+    if is_jdk_home(&canonical_dir) {
+        found.push(canonical_dir);
+        return;
+    }
+
+    // macOS bundles keep JDK home under Contents/Home
+    let bundle_home = canonical_dir.join("Contents").join("Home");
+    if is_jdk_home(&bundle_home) {
+        scan_dir(&bundle_home, 0, found, visited);
+        return;
+    }
+
+    if depth == 0 {
+        return;
+    }
+
+    let mut children: Vec<PathBuf> = match fs::read_dir(&canonical_dir) {
+        Ok(value) => value
+            .filter_map(| entry | entry.ok())
+            .map(| entry | entry.path())
+            .filter(| path | path.is_dir())
+            .collect(),
+        Err(..) => return,
+    };
+    children.sort();
+
+    for child in children {
+        scan_dir(&child, depth - 1, found, visited);
+    }
+}
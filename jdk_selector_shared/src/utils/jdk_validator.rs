@@ -0,0 +1,214 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+pub enum JdkKind {
+    Jdk,
+    // Runtime only, so javac and other development tools are missing
+    Jre,
+}
+
+pub struct ValidatedJdkHome {
+    pub path: PathBuf,
+    pub kind: JdkKind,
+    // Whether given path was pointing bin folder, java binary or macOS bundle instead of JDK home
+    pub corrected: bool,
+}
+
+pub enum JdkValidationError {
+    NotFound(PathBuf),
+    NotDirectory(PathBuf),
+    MissingJava(PathBuf),
+    // Registering JDK Selector itself would make executors run themselves forever
+    SelectorInstallDir(PathBuf),
+}
+
+impl Display for JdkValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            JdkValidationError::NotFound(path) => {
+                write!(f, "{} doesn't exist.", path.display())
+            }
+            JdkValidationError::NotDirectory(path) => {
+                write!(f, "{} isn't a directory.", path.display())
+            }
+            JdkValidationError::MissingJava(path) => {
+                write!(f, "Couldn't find bin/java in {}. Is it JDK home?", path.display())
+            }
+            JdkValidationError::SelectorInstallDir(path) => {
+                write!(f, "{} points to where JDK Selector is installed, not JDK.", path.display())
+            }
+        }
+    }
+}
+
+pub fn tool_path(jdk_path: &Path, tool: &str) -> PathBuf {
+    return jdk_path.join("bin").join(format!("{}{}", tool, std::env::consts::EXE_SUFFIX));
+}
+
+// Checks if path is JDK home and corrects common mistakes like appending bin to it
+pub fn validate_jdk_home(path: &Path, install_dir: Option<&Path>) -> Result<ValidatedJdkHome, JdkValidationError> {
+    // Relative path would be resolved against whichever directory executors run in.
+    // Symbolic links are kept since ones like default-java are chosen to follow upgrades
+    let path = match std::path::absolute(path) {
+        Ok(value) => value,
+        Err(..) => return Err(JdkValidationError::NotFound(path.to_path_buf())),
+    };
+
+    if !path.exists() {
+        return Err(JdkValidationError::NotFound(path.to_path_buf()));
+    }
+
+    let mut jdk_path = path.to_path_buf();
+    let mut corrected = false;
+
+    // .../bin/java -> .../bin
+    if jdk_path.is_file() && is_named(jdk_path.parent(), "bin") {
+        jdk_path.pop();
+        corrected = true;
+    }
+
+    if !jdk_path.is_dir() {
+        return Err(JdkValidationError::NotDirectory(path.to_path_buf()));
+    }
+
+    // .../bin -> ...
+    if is_named(Some(&jdk_path), "bin") && tool_path(jdk_path.parent().unwrap_or(&jdk_path), "java").is_file() {
+        jdk_path.pop();
+        corrected = true;
+    }
+
+    // .../Foo.jdk or .../Foo.jdk/Contents -> .../Foo.jdk/Contents/Home
+    for bundle_home in [jdk_path.join("Contents").join("Home"), jdk_path.join("Home")] {
+        if !tool_path(&jdk_path, "java").is_file() && tool_path(&bundle_home, "java").is_file() {
+            jdk_path = bundle_home;
+            corrected = true;
+        }
+    }
+
+    if !tool_path(&jdk_path, "java").is_file() {
+        return Err(JdkValidationError::MissingJava(jdk_path));
+    }
+
+    if let Some(install_dir) = install_dir.and_then(| value | value.canonicalize().ok()) {
+        let points_install_dir = [jdk_path.to_path_buf(), jdk_path.join("bin"), tool_path(&jdk_path, "java")]
+            .iter()
+            .filter_map(| value | value.canonicalize().ok())
+            .any(| value | value == install_dir || value.parent() == Some(&install_dir));
+
+        if points_install_dir {
+            return Err(JdkValidationError::SelectorInstallDir(jdk_path));
+        }
+    }
+
+    let kind = if tool_path(&jdk_path, "javac").is_file() { JdkKind::Jdk } else { JdkKind::Jre };
+
+    return Ok(ValidatedJdkHome {
+        path: jdk_path,
+        kind,
+        corrected,
+    });
+}
+
+fn is_named(path: Option<&Path>, name: &str) -> bool {
+    return path.and_then(| value | value.file_name())
+        .is_some_and(| value | value.eq_ignore_ascii_case(name));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+    use super::*;
+
+    // Directory with empty files at given paths, which is enough since only existence is checked
+    fn temp_layout(name: &str, files: &[PathBuf]) -> PathBuf {
+        let dir = env::temp_dir().join(format!("jdk-selector-test-{}-{}", process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+
+        for file in files {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+
+        return dir;
+    }
+
+    fn jdk_files(home: &Path) -> Vec<PathBuf> {
+        return vec![tool_path(home, "java"), tool_path(home, "javac")];
+    }
+
+    #[test]
+    fn jdk_home_is_accepted_as_is() {
+        let dir = temp_layout("validator-home", &jdk_files(Path::new("jdk")));
+        let jdk_home = dir.join("jdk");
+
+        let validated = validate_jdk_home(&jdk_home, None).ok().unwrap();
+        assert_eq!(validated.path, jdk_home);
+        assert!(matches!(validated.kind, JdkKind::Jdk));
+        assert!(!validated.corrected);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn bin_folder_and_java_binary_are_corrected_to_jdk_home() {
+        let dir = temp_layout("validator-bin", &jdk_files(Path::new("jdk")));
+        let jdk_home = dir.join("jdk");
+
+        for path in [jdk_home.join("bin"), tool_path(&jdk_home, "java")] {
+            let validated = validate_jdk_home(&path, None).ok().unwrap();
+            assert_eq!(validated.path, jdk_home);
+            assert!(validated.corrected);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn macos_bundle_is_corrected_to_contents_home() {
+        let bundle_home = Path::new("temurin-17.jdk").join("Contents").join("Home");
+        let dir = temp_layout("validator-bundle", &jdk_files(&bundle_home));
+
+        for path in [dir.join("temurin-17.jdk"), dir.join("temurin-17.jdk").join("Contents")] {
+            let validated = validate_jdk_home(&path, None).ok().unwrap();
+            assert_eq!(validated.path, dir.join(&bundle_home));
+            assert!(validated.corrected);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn runtime_without_javac_is_jre() {
+        let dir = temp_layout("validator-jre", &[tool_path(Path::new("jre"), "java")]);
+
+        let validated = validate_jdk_home(&dir.join("jre"), None).ok().unwrap();
+        assert!(matches!(validated.kind, JdkKind::Jre));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn folder_without_java_is_rejected() {
+        let dir = temp_layout("validator-missing", &[Path::new("empty").join("README")]);
+
+        assert!(matches!(validate_jdk_home(&dir.join("empty"), None), Err(JdkValidationError::MissingJava(..))));
+        assert!(matches!(validate_jdk_home(&dir.join("none"), None), Err(JdkValidationError::NotFound(..))));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn selector_install_dir_is_rejected() {
+        // Executors are named after tools, so install dir looks like bin folder of JDK
+        let dir = temp_layout("validator-install-dir", &[tool_path(Path::new("jdk-selector"), "java")]);
+        let install_dir = dir.join("jdk-selector").join("bin");
+
+        for path in [dir.join("jdk-selector"), install_dir.clone()] {
+            let validated = validate_jdk_home(&path, Some(&install_dir));
+            assert!(matches!(validated, Err(JdkValidationError::SelectorInstallDir(..))));
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::io;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::utils::config_loader::file_path;
+use crate::utils::recursion_guard::is_executor;
+
+// Broken JDKs or wrappers waiting on something mustn't hang add, scan or refresh
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+const PROPERTY_INDENT: usize = 4;
+
+// System properties printed by java -XshowSettings:properties
+pub struct ProbedProperties {
+    pub java_version: Option<String>,
+    pub java_runtime_version: Option<String>,
+    pub java_vendor: Option<String>,
+    pub os_arch: Option<String>,
+    pub java_vm_name: Option<String>,
+}
+
+pub fn java_binary_path(jdk_path: &Path) -> PathBuf {
+    return jdk_path.join("bin").join(format!("java{}", std::env::consts::EXE_SUFFIX));
+}
+
+// Runs JVM of given JDK, so it's only for JDKs without usable release file
+pub fn probe_jvm(jdk_path: &Path) -> io::Result<ProbedProperties> {
+    let java_path = java_binary_path(jdk_path);
+
+    // Executor runs whichever JDK is selected, so its properties wouldn't be of this JDK
+    if let Ok(executor_path) = file_path() {
+        if is_executor(&java_path, &executor_path) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} is JDK Selector's executor", java_path.display()),
+            ));
+        }
+    }
+
+    let mut child = Command::new(&java_path)
+        .args(["-XshowSettings:properties", "-version"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Settings are printed to stderr. It's read on another thread so java never blocks on full pipe,
+    // and so that processes started by java keeping the pipe open can't hang probing either
+    let (sender, receiver) = mpsc::channel();
+    if let Some(mut stderr) = child.stderr.take() {
+        thread::spawn(move || {
+            let mut raw: Vec<u8> = vec![];
+            let _ = stderr.read_to_end(&mut raw);
+            let _ = sender.send(raw);
+        });
+    }
+
+    let started = Instant::now();
+    while child.try_wait()?.is_none() {
+        if started.elapsed() >= PROBE_TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(timed_out());
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    let raw = match receiver.recv_timeout(PROBE_TIMEOUT.saturating_sub(started.elapsed())) {
+        Ok(value) => value,
+        Err(..) => return Err(timed_out()),
+    };
+    let properties = parse_properties(&String::from_utf8_lossy(&raw));
+    if properties.java_version.is_none() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "java didn't print its system properties",
+        ));
+    }
+
+    return Ok(properties);
+}
+
+fn timed_out() -> io::Error {
+    return io::Error::new(
+        io::ErrorKind::TimedOut,
+        format!("java didn't finish printing its system properties in {} seconds", PROBE_TIMEOUT.as_secs()),
+    );
+}
+
+pub fn parse_properties(raw: &str) -> ProbedProperties {
+    // Properties are indented by 4 spaces and continuation lines of multi-line values by 8.
+    // Continuation lines are skipped since they may contain separator too, like paths with " = " in them
+    let properties: HashMap<&str, &str> = raw.lines()
+        .filter(| line | line.len() - line.trim_start().len() <= PROPERTY_INDENT)
+        .filter_map(| line | line.split_once(" = "))
+        .map(| (key, value) | (key.trim(), value.trim()))
+        .collect();
+    let property = | key: &str | properties.get(key)
+        .filter(| value | !value.is_empty())
+        .map(| value | value.to_string());
+
+    return ProbedProperties {
+        java_version: property("java.version"),
+        java_runtime_version: property("java.runtime.version"),
+        java_vendor: property("java.vendor"),
+        os_arch: property("os.arch"),
+        java_vm_name: property("java.vm.name"),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn properties_are_parsed_from_show_settings_output() {
+        let raw = concat!(
+            "Property settings:\n",
+            "    file.encoding = UTF-8\n",
+            "    java.class.path = \n",
+            "    java.library.path = /usr/java/packages/lib\n",
+            "        /usr/lib64\n",
+            "        /lib64\n",
+            "    java.runtime.version = 17.0.9+9\n",
+            "    java.vendor = Eclipse Adoptium\n",
+            "    java.version = 17.0.9\n",
+            "    java.vm.name = OpenJDK 64-Bit Server VM\n",
+            "    os.arch = amd64\n",
+            "\n",
+            "openjdk version \"17.0.9\" 2023-10-17\n",
+            "OpenJDK Runtime Environment Temurin-17.0.9+9 (build 17.0.9+9)\n",
+        );
+
+        let properties = parse_properties(raw);
+        assert_eq!(properties.java_version.as_deref(), Some("17.0.9"));
+        assert_eq!(properties.java_runtime_version.as_deref(), Some("17.0.9+9"));
+        assert_eq!(properties.java_vendor.as_deref(), Some("Eclipse Adoptium"));
+        assert_eq!(properties.os_arch.as_deref(), Some("amd64"));
+        assert_eq!(properties.java_vm_name.as_deref(), Some("OpenJDK 64-Bit Server VM"));
+    }
+
+    #[test]
+    fn continuation_lines_are_not_read_as_properties() {
+        let raw = concat!(
+            "Property settings:\n",
+            "    java.library.path = /usr/lib64\n",
+            "        java.version = 1.2 is part of the previous value\n",
+            "        /lib64\n",
+            "    os.arch = aarch64\n",
+        );
+
+        let properties = parse_properties(raw);
+        assert!(properties.java_version.is_none());
+        assert_eq!(properties.os_arch.as_deref(), Some("aarch64"));
+    }
+}
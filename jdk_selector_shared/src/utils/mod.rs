@@ -1 +1,16 @@
-pub mod config_loader;
\ No newline at end of file
+pub mod config_loader;
+pub mod config_recovery;
+pub mod java_version;
+pub mod jdk_metadata;
+pub mod jdk_scanner;
+pub mod jdk_validator;
+pub mod jvm_prober;
+pub mod project_file;
+pub mod recursion_guard;
+pub mod release_file;
+pub mod resolution_cache;
+pub mod resolver;
+pub mod shim;
+pub mod trace;
+pub mod vendor;
+pub mod version_constraint;
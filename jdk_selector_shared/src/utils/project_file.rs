@@ -0,0 +1,30 @@
+use std::{fs, io};
+use std::path::{Path, PathBuf};
+
+pub const PROJECT_FILE_NAME: &str = ".jdk-version";
+
+// Walks up from given directory and returns the nearest project file
+pub fn find_project_file(dir: &Path) -> Option<PathBuf> {
+    return dir.ancestors()
+        .map(| value | value.join(PROJECT_FILE_NAME))
+        .find(| value | value.is_file());
+}
+
+// Returns the first line which isn't empty or comment
+pub fn read_project_file(path: &Path) -> io::Result<Option<String>> {
+    let raw = fs::read_to_string(path)?;
+
+    return Ok(
+        raw.lines()
+            .map(| value | value.trim())
+            .find(| value | !value.is_empty() && !value.starts_with('#'))
+            .map(| value | value.to_string())
+    );
+}
+
+pub fn write_project_file(dir: &Path, selector: &str) -> io::Result<PathBuf> {
+    let path = dir.join(PROJECT_FILE_NAME);
+    fs::write(&path, format!("{}\n", selector))?;
+
+    return Ok(path);
+}
@@ -0,0 +1,33 @@
+use std::env;
+use std::path::Path;
+
+// Counts nested executor runs. Passed down to children so a JDK tool resolving back into an
+// executor through wrapper scripts or links is noticed before it loops forever
+pub const RECURSION_DEPTH_ENV: &str = "JDK_SELECTOR_DEPTH";
+
+// Legitimate nesting like build tools running java which runs javac stays far below this
+pub const MAX_RECURSION_DEPTH: u32 = 16;
+
+pub fn recursion_depth() -> u32 {
+    return env::var(RECURSION_DEPTH_ENV)
+        .ok()
+        .and_then(| value | value.trim().parse().ok())
+        .unwrap_or(0);
+}
+
+// Checks whether target is one of executors in install dir, following symbolic links
+pub fn is_executor(target: &Path, executor_path: &Path) -> bool {
+    let target = match target.canonicalize() {
+        Ok(value) => value,
+        Err(..) => return false,
+    };
+
+    if target == executor_path {
+        return true;
+    }
+
+    return match (target.parent(), executor_path.parent()) {
+        (Some(target_dir), Some(install_dir)) => target_dir == install_dir,
+        _ => false,
+    };
+}
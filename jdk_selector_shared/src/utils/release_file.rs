@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+use std::{fs, io};
+use std::path::Path;
+
+// Properties from $JAVA_HOME/release, which is shipped with JDK 9+ and most JDK 8 builds
+pub struct ReleaseInfo {
+    pub java_version: Option<String>,
+    pub java_runtime_version: Option<String>,
+    pub implementor: Option<String>,
+    pub implementor_version: Option<String>,
+    pub os_name: Option<String>,
+    pub os_arch: Option<String>,
+    pub jvm_variant: Option<String>,
+    pub modules: Vec<String>,
+    pub properties: HashMap<String, String>,
+}
+
+pub fn read_release_file(jdk_path: &Path) -> io::Result<ReleaseInfo> {
+    let raw = fs::read_to_string(jdk_path.join("release"))?;
+
+    return Ok(parse_release_file(&raw));
+}
+
+pub fn parse_release_file(raw: &str) -> ReleaseInfo {
+    let properties: HashMap<String, String> = raw.lines()
+        .filter(| line | !line.trim_start().starts_with('#'))
+        .filter_map(| line | line.split_once('='))
+        .map(| (key, value) | (key.trim().to_string(), unquote(value.trim()).to_string()))
+        .collect();
+    let property = | key: &str | properties.get(key)
+        .filter(| value | !value.is_empty())
+        .map(| value | value.to_string());
+
+    return ReleaseInfo {
+        java_version: property("JAVA_VERSION"),
+        java_runtime_version: property("JAVA_RUNTIME_VERSION"),
+        implementor: property("IMPLEMENTOR"),
+        implementor_version: property("IMPLEMENTOR_VERSION"),
+        os_name: property("OS_NAME"),
+        os_arch: property("OS_ARCH"),
+        jvm_variant: property("JVM_VARIANT"),
+        modules: property("MODULES")
+            .map(| value | value.split_whitespace().map(| v | v.to_string()).collect())
+            .unwrap_or_default(),
+        properties,
+    };
+}
+
+fn unquote(value: &str) -> &str {
+    return value.strip_prefix('"')
+        .and_then(| v | v.strip_suffix('"'))
+        .unwrap_or(value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_and_unquoted_values_are_read() {
+        let info = parse_release_file(concat!(
+            "IMPLEMENTOR=\"Eclipse Adoptium\"\n",
+            "JAVA_VERSION=\"17.0.9\"\n",
+            "JAVA_RUNTIME_VERSION=\"17.0.9+9\"\n",
+            "OS_ARCH=x86_64\n",
+            "JVM_VARIANT = Hotspot \n",
+            "MODULES=\"java.base java.logging\"\n",
+        ));
+
+        assert_eq!(info.implementor.as_deref(), Some("Eclipse Adoptium"));
+        assert_eq!(info.java_version.as_deref(), Some("17.0.9"));
+        assert_eq!(info.java_runtime_version.as_deref(), Some("17.0.9+9"));
+        assert_eq!(info.os_arch.as_deref(), Some("x86_64"));
+        assert_eq!(info.jvm_variant.as_deref(), Some("Hotspot"));
+        assert_eq!(info.modules, ["java.base", "java.logging"]);
+    }
+
+    #[test]
+    fn malformed_and_blank_lines_are_skipped() {
+        let info = parse_release_file(concat!(
+            "\n",
+            "   \n",
+            "# JAVA_VERSION=\"1.2\"\n",
+            "not a property\n",
+            "IMPLEMENTOR=\"\"\n",
+            "JAVA_VERSION=\n",
+            "SOURCE=\".:git:abc=def\"\n",
+        ));
+
+        assert!(info.implementor.is_none());
+        assert!(info.java_version.is_none());
+        assert!(info.java_runtime_version.is_none());
+        assert_eq!(info.properties.get("SOURCE").map(| v | v.as_str()), Some(".:git:abc=def"));
+        assert_eq!(info.properties.len(), 3);
+    }
+}
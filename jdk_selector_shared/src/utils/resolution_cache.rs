@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use crate::models::config::Config;
+use crate::models::jdk_info::JdkInfo;
+use crate::utils::config_loader::CONFIG_SCHEMA_VERSION;
+use crate::utils::project_file::find_project_file;
+use crate::utils::resolver::{env_override, layer_kinds, LayerKind, Selection, SelectionSource};
+use crate::utils::version_constraint::find_jdk_info_quietly;
+
+const CACHE_HEADER: &str = "jdk-selector-cache";
+
+// Tab separated lines precomputed from config so executors skip parsing JSON and matching constraints.
+// Only global selection and per-tool overrides are cached, since others depend on environment
+pub struct ResolutionCache {
+    pub export_java_home: bool,
+    jdk_info_list: Vec<JdkInfo>,
+    global: Option<usize>,
+    // None means override couldn't be resolved, which is left to full resolution to report
+    tool_overrides: HashMap<String, Option<usize>>,
+}
+
+impl ResolutionCache {
+    // Walks the same layers as full resolution. Returns None whenever full resolution is needed,
+    // like JDK_SELECTOR_JDK or project file being present
+    pub fn resolve(&self, tool: &str, current_dir: &Path) -> Option<Selection<'_>> {
+        for kind in layer_kinds(Some(tool)) {
+            match kind {
+                LayerKind::Environment => {
+                    if env_override().is_some() {
+                        return None;
+                    }
+                },
+                LayerKind::ToolOverride(tool) => {
+                    if let Some(index) = self.tool_overrides.get(&tool) {
+                        return Some(Selection {
+                            jdk_info: &self.jdk_info_list[(*index)?],
+                            source: SelectionSource::ToolOverride(tool),
+                        });
+                    }
+                },
+                LayerKind::ProjectFile => {
+                    if find_project_file(current_dir).is_some() {
+                        return None;
+                    }
+                },
+                LayerKind::Global => {
+                    return Some(Selection {
+                        jdk_info: &self.jdk_info_list[self.global?],
+                        source: SelectionSource::Global,
+                    });
+                },
+            };
+        }
+
+        return None;
+    }
+}
+
+pub fn cache_path(config_path: &Path) -> PathBuf {
+    return config_path.with_extension("cache");
+}
+
+// stamp has to be taken from config file before config is read from it. Taking it afterwards would let
+// config saved in between look like the one cache is made from, keeping stale cache forever
+pub fn write_cache(config_path: &Path, stamp: &str, config: &Config) -> io::Result<()> {
+    let path = cache_path(config_path);
+    let schema_version = CONFIG_SCHEMA_VERSION.to_string();
+    let export_java_home = if config.export_java_home { "1" } else { "0" };
+
+    let global = config.selected_jdk.as_deref().and_then(| value | find_jdk_info_quietly(config, value));
+    let overrides: Vec<(&String, Option<&JdkInfo>)> = config.tool_overrides.iter()
+        .map(| (tool, selector) | (tool, find_jdk_info_quietly(config, selector)))
+        .collect();
+
+    let mut lines: Vec<Vec<&str>> = vec![
+        vec![CACHE_HEADER, &schema_version, stamp],
+        vec!["export_java_home", export_java_home],
+    ];
+
+    let mut jdk_names: Vec<&str> = vec![];
+    for jdk_info in global.iter().chain(overrides.iter().filter_map(| (_, value) | value.as_ref())) {
+        if jdk_names.contains(&jdk_info.name.as_str()) {
+            continue;
+        }
+        jdk_names.push(&jdk_info.name);
+
+        lines.push(vec!["jdk", &jdk_info.name, &jdk_info.path]);
+        for (key, value) in jdk_info.env.iter() {
+            lines.push(vec!["env", key, value]);
+        }
+        for option in jdk_info.java_options.iter() {
+            lines.push(vec!["option", option]);
+        }
+    }
+
+    if let Some(jdk_info) = global {
+        lines.push(vec!["global", &jdk_info.name]);
+    }
+    for (tool, jdk_info) in overrides.iter() {
+        lines.push(vec!["tool", tool, jdk_info.map(| value | value.name.as_str()).unwrap_or("")]);
+    }
+
+    // Values which would break line format are left to full resolution
+    if lines[1..].iter().flatten().any(| value | value.contains(['\t', '\n', '\r'])) {
+        return match fs::remove_file(&path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+
+    let raw: Vec<String> = lines.iter().map(| value | value.join("\t")).collect();
+    let temp_path = path.with_extension(format!("cache.tmp-{}", std::process::id()));
+    fs::write(&temp_path, raw.join("\n"))?;
+
+    return fs::rename(&temp_path, &path);
+}
+
+// Returns None if cache is missing, stale or unreadable
+pub fn read_cache(config_path: &Path) -> Option<ResolutionCache> {
+    let raw = fs::read_to_string(cache_path(config_path)).ok()?;
+    let mut lines = raw.lines();
+
+    let expected_header = format!("{}\t{}\t{}", CACHE_HEADER, CONFIG_SCHEMA_VERSION, config_stamp(config_path).ok()?);
+    if lines.next()? != expected_header {
+        return None;
+    }
+
+    let mut cache = ResolutionCache {
+        export_java_home: true,
+        jdk_info_list: vec![],
+        global: None,
+        tool_overrides: HashMap::new(),
+    };
+
+    for line in lines {
+        let mut fields = line.split('\t');
+        match (fields.next()?, fields.next(), fields.next()) {
+            ("export_java_home", Some(value), None) => cache.export_java_home = value == "1",
+            ("jdk", Some(name), Some(path)) => cache.jdk_info_list.push(JdkInfo::new(name, path)),
+            ("env", Some(key), Some(value)) => {
+                cache.jdk_info_list.last_mut()?.env.insert(key.to_string(), value.to_string());
+            },
+            ("option", Some(value), None) => cache.jdk_info_list.last_mut()?.java_options.push(value.to_string()),
+            ("global", Some(name), None) => cache.global = Some(index_of(&cache.jdk_info_list, name)?),
+            ("tool", Some(tool), Some(name)) => {
+                let index = if name.is_empty() { None } else { Some(index_of(&cache.jdk_info_list, name)?) };
+                cache.tool_overrides.insert(tool.to_string(), index);
+            },
+            _ => return None,
+        };
+    }
+
+    return Some(cache);
+}
+
+fn index_of(jdk_info_list: &[JdkInfo], name: &str) -> Option<usize> {
+    return jdk_info_list.iter().position(| value | value.name == name);
+}
+
+// Modified time in nanoseconds and size of config, which change whenever config is written
+pub fn config_stamp(config_path: &Path) -> io::Result<String> {
+    let metadata = fs::metadata(config_path)?;
+    let modified = metadata.modified()?
+        .duration_since(UNIX_EPOCH)
+        .map(| value | value.as_nanos())
+        .unwrap_or(0);
+
+    return Ok(format!("{}\t{}", modified, metadata.len()));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::env;
+    use std::process;
+    use crate::utils::project_file::PROJECT_FILE_NAME;
+    use crate::utils::resolver::{resolve_tool, JDK_OVERRIDE_ENV};
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("jdk-selector-test-{}-{}", process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        return dir;
+    }
+
+    // Cache may leave resolution to full path, but whatever it resolves has to be the same
+    fn assert_agrees(cache: &ResolutionCache, config: &Config, tool: &str, current_dir: &Path, cached: bool) {
+        let full = resolve_tool(config, current_dir, tool).ok().unwrap();
+
+        match cache.resolve(tool, current_dir) {
+            Some(selection) => {
+                assert!(cached, "{} shouldn't be resolved from cache", tool);
+                assert_eq!(selection.jdk_info.name, full.jdk_info.name);
+                assert_eq!(selection.source.to_string(), full.source.to_string());
+            },
+            None => assert!(!cached, "{} should be resolved from cache", tool),
+        };
+    }
+
+    #[test]
+    fn cache_resolves_every_layer_as_full_resolution() {
+        let dir = temp_dir("cache-layers");
+        let config_path = dir.join("config.json");
+        fs::write(&config_path, "{}").unwrap();
+
+        let config = Config {
+            selected_jdk: Some("global".to_string()),
+            jdk_info_list: ["global", "tool", "project", "environment"].iter()
+                .map(| value | JdkInfo::new(value, &format!("/opt/{}", value)))
+                .collect(),
+            tool_overrides: BTreeMap::from([("javac".to_string(), "tool".to_string())]),
+            ..Config::default()
+        };
+        write_cache(&config_path, &config_stamp(&config_path).unwrap(), &config).unwrap();
+        let cache = read_cache(&config_path).unwrap();
+
+        let project_dir = dir.join("project");
+        fs::create_dir_all(&project_dir).unwrap();
+        fs::write(project_dir.join(PROJECT_FILE_NAME), "project").unwrap();
+
+        env::remove_var(JDK_OVERRIDE_ENV);
+        assert_agrees(&cache, &config, "java", &dir, true);
+        assert_agrees(&cache, &config, "javac", &dir, true);
+        assert_agrees(&cache, &config, "java", &project_dir, false);
+        assert_agrees(&cache, &config, "javac", &project_dir, true);
+
+        env::set_var(JDK_OVERRIDE_ENV, "environment");
+        assert_agrees(&cache, &config, "java", &dir, false);
+        assert_agrees(&cache, &config, "javac", &project_dir, false);
+        env::remove_var(JDK_OVERRIDE_ENV);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn cache_of_config_saved_meanwhile_is_stale() {
+        let dir = temp_dir("cache-stamp");
+        let config_path = dir.join("config.json");
+
+        fs::write(&config_path, "{}").unwrap();
+        let stamp = config_stamp(&config_path).unwrap();
+        let config = Config::default();
+        // Another process saves config after it's read but before cache is written
+        fs::write(&config_path, "{\"selected_jdk\": null}").unwrap();
+
+        write_cache(&config_path, &stamp, &config).unwrap();
+        assert!(read_cache(&config_path).is_none());
+
+        write_cache(&config_path, &config_stamp(&config_path).unwrap(), &config).unwrap();
+        assert!(read_cache(&config_path).is_some());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,232 @@
+use std::{env, io};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use crate::models::config::Config;
+use crate::models::jdk_info::JdkInfo;
+use crate::trace;
+use crate::utils::project_file::{find_project_file, read_project_file};
+use crate::utils::version_constraint::{find_jdk_info, newest_jdk_info};
+
+// Pins JDK for current process and its children, taking priority over any config
+pub const JDK_OVERRIDE_ENV: &str = "JDK_SELECTOR_JDK";
+
+pub enum SelectionSource {
+    Environment,
+    ToolOverride(String),
+    ProjectFile(PathBuf),
+    Global,
+}
+
+impl Display for SelectionSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            SelectionSource::Environment => write!(f, "{} environment variable", JDK_OVERRIDE_ENV),
+            SelectionSource::ToolOverride(tool) => write!(f, "override for {}", tool),
+            SelectionSource::ProjectFile(path) => write!(f, "project file {}", path.display()),
+            SelectionSource::Global => write!(f, "global selection"),
+        }
+    }
+}
+
+pub struct Selection<'a> {
+    pub jdk_info: &'a JdkInfo,
+    pub source: SelectionSource,
+}
+
+pub enum ResolveError {
+    NotSelected,
+    NotFound(String, SelectionSource),
+    IOError(io::Error, PathBuf),
+}
+
+impl Display for ResolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            ResolveError::NotSelected => {
+                write!(f, "No JDK is selected.")
+            }
+            ResolveError::NotFound(name, source) => {
+                write!(f, "{} is selected by {} but couldn't be found from config.", name, source)
+            }
+            ResolveError::IOError(e, path) => {
+                write!(f, "Couldn't read {}: {}", path.display(), e)
+            }
+        }
+    }
+}
+
+// One of places JDK can be selected from, in order of priority
+pub enum LayerKind {
+    Environment,
+    ToolOverride(String),
+    ProjectFile,
+    Global,
+}
+
+impl Display for LayerKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            LayerKind::Environment => write!(f, "{} environment variable", JDK_OVERRIDE_ENV),
+            LayerKind::ToolOverride(tool) => write!(f, "override for {}", tool),
+            LayerKind::ProjectFile => write!(f, "project file"),
+            LayerKind::Global => write!(f, "global selection"),
+        }
+    }
+}
+
+pub struct Layer {
+    pub kind: LayerKind,
+    // Selector and where exactly it's from, or None if the layer doesn't select anything
+    pub found: Result<Option<(SelectionSource, String)>, ResolveError>,
+}
+
+// Order layers are considered in for tool, or for any tool if None.
+// Executors, resolution cache, which and --explain all follow this so they can never disagree
+pub fn layer_kinds(tool: Option<&str>) -> Vec<LayerKind> {
+    let mut kinds = vec![LayerKind::Environment];
+    if let Some(tool) = tool {
+        kinds.push(LayerKind::ToolOverride(tool.to_string()));
+    }
+    kinds.push(LayerKind::ProjectFile);
+    kinds.push(LayerKind::Global);
+
+    return kinds;
+}
+
+// Every layer considered for tool, or for any tool if None, along with what it selects
+pub fn layers(config: &Config, current_dir: &Path, tool: Option<&str>) -> Vec<Layer> {
+    return layer_kinds(tool).into_iter()
+        .map(| kind | {
+            let found = match &kind {
+                LayerKind::Environment => Ok(env_override().map(| value | (SelectionSource::Environment, value))),
+                LayerKind::ToolOverride(tool) => Ok(config.tool_overrides.get(tool)
+                    .map(| value | (SelectionSource::ToolOverride(tool.to_string()), value.to_string()))),
+                // Empty project file doesn't pin anything
+                LayerKind::ProjectFile => match find_project_file(current_dir) {
+                    Some(path) => match read_project_file(&path) {
+                        Ok(value) => Ok(value.map(| value | (SelectionSource::ProjectFile(path), value))),
+                        Err(e) => Err(ResolveError::IOError(e, path)),
+                    },
+                    None => Ok(None),
+                },
+                LayerKind::Global => Ok(config.selected_jdk.clone().map(| value | (SelectionSource::Global, value))),
+            };
+
+            Layer { kind, found }
+        })
+        .collect();
+}
+
+// Determines JDK to use by name or version constraint in order of environment variable,
+// project file found from current directory and global selection
+pub fn resolve<'a>(config: &'a Config, current_dir: &Path) -> Result<Selection<'a>, ResolveError> {
+    return resolve_layers(config, layers(config, current_dir, None));
+}
+
+// Same as resolve but per-tool override of given tool comes right after environment variable
+pub fn resolve_tool<'a>(config: &'a Config, current_dir: &Path, tool: &str) -> Result<Selection<'a>, ResolveError> {
+    return resolve_layers(config, layers(config, current_dir, Some(tool)));
+}
+
+// First layer selecting anything wins
+fn resolve_layers(config: &Config, layers: Vec<Layer>) -> Result<Selection<'_>, ResolveError> {
+    for layer in layers {
+        if let Some((source, selector)) = layer.found? {
+            return select(config, &selector, source);
+        }
+    }
+
+    return Err(ResolveError::NotSelected);
+}
+
+pub struct ToolResolution<'a> {
+    pub selection: Selection<'a>,
+    pub binary: PathBuf,
+    // Originally selected JDK if it doesn't have the tool and another JDK is used instead
+    pub fallback_from: Option<&'a JdkInfo>,
+}
+
+pub enum ToolResolveError<'a> {
+    Resolve(ResolveError),
+    MissingTool {
+        file_name: String,
+        selection: Selection<'a>,
+        providers: Vec<&'a JdkInfo>,
+    },
+}
+
+impl Display for ToolResolveError<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            ToolResolveError::Resolve(e) => e.fmt(f),
+            ToolResolveError::MissingTool { file_name, selection, providers } => {
+                write!(f, "{} doesn't exist in {} selected by {}.", file_name, selection.jdk_info, selection.source)?;
+                if providers.is_empty() {
+                    return write!(f, "\nNone of registered JDKs provides it.");
+                }
+
+                let names: Vec<&str> = providers.iter().map(| value | value.name.as_str()).collect();
+                write!(f, "\nIt's provided by: {}", names.join(", "))?;
+                write!(f, "\nRun 'jdk_selector_cli setting tool_fallback true' to use the newest of them instead.")
+            }
+        }
+    }
+}
+
+// Resolves binary of tool named file_name, like java or javac.exe, as executor runs it
+pub fn resolve_tool_binary<'a>(
+    config: &'a Config,
+    current_dir: &Path,
+    file_name: &str,
+) -> Result<ToolResolution<'a>, ToolResolveError<'a>> {
+    // Overrides are keyed by tool name without .exe
+    let tool = file_name.strip_suffix(env::consts::EXE_SUFFIX).unwrap_or(file_name);
+    let selection = match resolve_tool(config, current_dir, tool) {
+        Ok(value) => value,
+        Err(e) => return Err(ToolResolveError::Resolve(e)),
+    };
+
+    let binary = Path::new(&selection.jdk_info.path).join("bin").join(file_name);
+    if binary.is_file() {
+        return Ok(ToolResolution { selection, binary, fallback_from: None });
+    }
+
+    // Executors exist for tools removed from recent JDKs as well, like javah or jjs
+    let providers = jdk_info_providing(config, file_name);
+    let fallback = match newest_jdk_info(config, providers.iter().copied()) {
+        Some(value) if config.tool_fallback => value,
+        _ => return Err(ToolResolveError::MissingTool { file_name: file_name.to_string(), selection, providers }),
+    };
+    trace!("{} doesn't exist in {}, falling back to {}", file_name, selection.jdk_info.name, fallback.name);
+
+    return Ok(ToolResolution {
+        selection: Selection {
+            jdk_info: fallback,
+            source: selection.source,
+        },
+        binary: Path::new(&fallback.path).join("bin").join(file_name),
+        fallback_from: Some(selection.jdk_info),
+    });
+}
+
+fn select<'a>(config: &'a Config, selector: &str, source: SelectionSource) -> Result<Selection<'a>, ResolveError> {
+    trace!("'{}' is given by {}", selector, source);
+    return match find_jdk_info(config, selector) {
+        Some(jdk_info) => Ok(Selection { jdk_info, source }),
+        None => Err(ResolveError::NotFound(selector.to_string(), source)),
+    };
+}
+
+// JDKs having given file in their bin folder, in order of config
+pub fn jdk_info_providing<'a>(config: &'a Config, file_name: &str) -> Vec<&'a JdkInfo> {
+    return config.jdk_info_list.iter()
+        .filter(| value | Path::new(&value.path).join("bin").join(file_name).is_file())
+        .collect();
+}
+
+pub fn env_override() -> Option<String> {
+    return env::var(JDK_OVERRIDE_ENV)
+        .ok()
+        .map(| value | value.trim().to_string())
+        .filter(| value | !value.is_empty());
+}
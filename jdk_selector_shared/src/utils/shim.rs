@@ -0,0 +1,132 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+use crate::models::config::Config;
+
+pub struct RehashResult {
+    pub created: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+// Union of executable file names in bin of every registered JDK, like java or javac.exe
+pub fn tool_file_names(config: &Config) -> BTreeSet<String> {
+    let mut names: BTreeSet<String> = BTreeSet::new();
+
+    for jdk_info in config.jdk_info_list.iter() {
+        // JDKs which have gone or can't be read just don't contribute any tool
+        let entries = match fs::read_dir(Path::new(&jdk_info.path).join("bin")) {
+            Ok(value) => value,
+            Err(..) => continue,
+        };
+
+        for entry in entries.flatten() {
+            if is_executable(&entry.path()) {
+                names.insert(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+    }
+
+    return names;
+}
+
+// Makes install dir have links to binary for exactly given names.
+// Files which aren't links to binary are never touched, and neither is binary itself
+pub fn rehash(binary_path: &Path, names: &BTreeSet<String>) -> io::Result<RehashResult> {
+    let binary_path = binary_path.canonicalize()?;
+    let install_dir = match binary_path.parent() {
+        Some(value) => value,
+        None => return Ok(RehashResult { created: vec![], removed: vec![] }),
+    };
+    let binary_name = binary_path.file_name().map(| value | value.to_string_lossy().to_string());
+
+    let mut existing: BTreeSet<String> = BTreeSet::new();
+    let mut removed: Vec<String> = vec![];
+    for entry in fs::read_dir(install_dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if Some(&name) == binary_name.as_ref() || !is_link_to(&entry.path(), &binary_path) {
+            continue;
+        }
+
+        if names.contains(&name) {
+            existing.insert(name);
+        } else {
+            fs::remove_file(entry.path())?;
+            removed.push(name);
+        }
+    }
+
+    let mut created: Vec<String> = vec![];
+    for name in names.iter() {
+        if existing.contains(name) || Some(name) == binary_name.as_ref() {
+            continue;
+        }
+
+        // Something else already has the name, like file put there by user
+        let link_path = install_dir.join(name);
+        if fs::symlink_metadata(&link_path).is_ok() {
+            continue;
+        }
+
+        link(&binary_path, &link_path)?;
+        created.push(name.to_string());
+    }
+
+    return Ok(RehashResult { created, removed });
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    return match fs::metadata(path) {
+        Ok(value) => value.is_file() && value.permissions().mode() & 0o111 != 0,
+        Err(..) => false,
+    };
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    let is_exe = path.extension().is_some_and(| value | value.eq_ignore_ascii_case("exe"));
+
+    return is_exe && path.is_file();
+}
+
+#[cfg(unix)]
+fn is_link_to(path: &Path, binary_path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    if path.canonicalize().is_ok_and(| value | value == binary_path) {
+        return true;
+    }
+
+    // Hard links share the same inode
+    return match (fs::symlink_metadata(path), fs::metadata(binary_path)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    };
+}
+
+// Hard links share metadata of the same file, so size and modified time are same as binary
+#[cfg(not(unix))]
+fn is_link_to(path: &Path, binary_path: &Path) -> bool {
+    return match (fs::metadata(path), fs::metadata(binary_path)) {
+        (Ok(a), Ok(b)) => a.is_file() && a.len() == b.len() && a.modified().ok() == b.modified().ok(),
+        _ => false,
+    };
+}
+
+// Relative symbolic link keeps working even if install dir is moved
+#[cfg(unix)]
+fn link(binary_path: &Path, link_path: &Path) -> io::Result<()> {
+    let target = binary_path.file_name().map(Path::new).unwrap_or(binary_path);
+
+    return std::os::unix::fs::symlink(target, link_path);
+}
+
+// Symbolic links require privilege on Windows, so hard links are used instead
+#[cfg(not(unix))]
+fn link(binary_path: &Path, link_path: &Path) -> io::Result<()> {
+    return fs::hard_link(binary_path, link_path);
+}
@@ -0,0 +1,27 @@
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+// Makes executors and CLI explain every resolution decision on stderr, even in release builds
+pub const TRACE_ENV: &str = "JDK_SELECTOR_TRACE";
+
+static TRACE_FORCED: AtomicBool = AtomicBool::new(false);
+static TRACE_ENV_SET: OnceLock<bool> = OnceLock::new();
+
+pub fn trace_enabled() -> bool {
+    if TRACE_FORCED.load(Ordering::Relaxed) {
+        return true;
+    }
+
+    return *TRACE_ENV_SET.get_or_init(|| {
+        return match env::var(TRACE_ENV) {
+            Ok(value) => !matches!(value.trim(), "" | "0" | "false"),
+            Err(..) => false,
+        };
+    });
+}
+
+// For --trace of CLI
+pub fn enable_trace() {
+    TRACE_FORCED.store(true, Ordering::Relaxed);
+}
@@ -0,0 +1,32 @@
+// Short lowercase names of well-known JDK distributions, matched against IMPLEMENTOR or java.vendor
+const VENDOR_ALIASES: [(&str, &str); 13] = [
+    ("adoptium", "temurin"),
+    ("adoptopenjdk", "adoptopenjdk"),
+    ("azul", "zulu"),
+    ("amazon", "corretto"),
+    ("bellsoft", "liberica"),
+    ("graalvm", "graalvm"),
+    ("microsoft", "microsoft"),
+    ("red hat", "redhat"),
+    ("sap", "sapmachine"),
+    ("ibm", "semeru"),
+    ("jetbrains", "jbr"),
+    ("oracle", "oracle"),
+    ("openjdk", "openjdk"),
+];
+
+pub fn vendor_alias(vendor: &str) -> String {
+    let lowercase_vendor = vendor.to_lowercase();
+
+    for (keyword, alias) in VENDOR_ALIASES.iter() {
+        if lowercase_vendor.contains(keyword) {
+            return alias.to_string();
+        }
+    }
+
+    // Unknown vendors are named by their first word like "Ubuntu" -> "ubuntu"
+    return lowercase_vendor.split(| v: char | !v.is_alphanumeric())
+        .find(| value | !value.is_empty())
+        .unwrap_or("jdk")
+        .to_string();
+}
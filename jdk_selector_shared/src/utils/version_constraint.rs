@@ -0,0 +1,262 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use crate::models::config::Config;
+use crate::models::jdk_info::JdkInfo;
+use crate::trace;
+use crate::utils::java_version::JavaVersion;
+use crate::utils::vendor::vendor_alias;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    // Bare version like 17 or 17.0, which matches every version starting with it
+    Prefix,
+    Equal,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+#[derive(Debug, Clone)]
+struct Comparator {
+    operator: Operator,
+    version: JavaVersion,
+}
+
+impl Comparator {
+    fn matches(&self, version: &JavaVersion) -> bool {
+        // Partial versions compare only given numbers so <17 excludes 17.0.8 and <=17 includes it
+        let ordering = version.cmp_prefix(&self.version);
+
+        // Pre-release or build given in constraint like 21-ea has to match as well
+        let qualifiers_match = (self.version.pre.is_none() || version.pre == self.version.pre)
+            && (self.version.build.is_none() || version.build == self.version.build);
+
+        return match self.operator {
+            Operator::Prefix => ordering == Ordering::Equal && qualifiers_match,
+            Operator::Equal => version.numbers.len() >= self.version.numbers.len()
+                && ordering == Ordering::Equal
+                && qualifiers_match,
+            Operator::Greater => ordering == Ordering::Greater,
+            Operator::GreaterOrEqual => ordering != Ordering::Less,
+            Operator::Less => ordering == Ordering::Less,
+            Operator::LessOrEqual => ordering != Ordering::Greater,
+        };
+    }
+}
+
+// Selector like 17, ">=11,<17", temurin@21 or temurin alone
+#[derive(Debug, Clone)]
+pub struct VersionConstraint {
+    vendor: Option<String>,
+    comparators: Vec<Comparator>,
+}
+
+#[derive(Debug)]
+pub struct InvalidConstraintError {
+    pub part: String,
+}
+
+impl Display for InvalidConstraintError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' isn't a valid version constraint", self.part)
+    }
+}
+
+impl VersionConstraint {
+    pub fn parse(raw: &str) -> Result<VersionConstraint, InvalidConstraintError> {
+        let raw = raw.trim();
+        let (vendor, versions) = match raw.split_once('@') {
+            Some((vendor, versions)) => (Some(vendor.trim()), versions.trim()),
+            // Selector without any digit is vendor alone like temurin
+            None if !raw.is_empty() && !raw.chars().any(| value | value.is_ascii_digit()) && raw != "*" => {
+                (Some(raw), "")
+            },
+            None => (None, raw),
+        };
+
+        let vendor = match vendor {
+            Some("") => return Err(InvalidConstraintError { part: raw.to_string() }),
+            Some(value) => Some(value.to_lowercase()),
+            None => None,
+        };
+
+        let mut comparators: Vec<Comparator> = vec![];
+        for part in versions.split(',').map(| value | value.trim()) {
+            if part.is_empty() || part == "*" {
+                continue;
+            }
+
+            comparators.push(parse_comparator(part)?);
+        }
+
+        if vendor.is_none() && comparators.is_empty() && versions != "*" {
+            return Err(InvalidConstraintError { part: raw.to_string() });
+        }
+
+        return Ok(VersionConstraint {
+            vendor,
+            comparators,
+        });
+    }
+
+    pub fn matches(&self, jdk_info: &JdkInfo) -> bool {
+        if let Some(vendor) = &self.vendor {
+            let matched = match &jdk_info.vendor {
+                Some(value) => vendor_alias(value) == *vendor || value.to_lowercase().contains(vendor.as_str()),
+                None => false,
+            };
+            if !matched {
+                return false;
+            }
+        }
+
+        if self.comparators.is_empty() {
+            return true;
+        }
+
+        return match jdk_info.version.as_deref().and_then(JavaVersion::parse) {
+            Some(version) => self.comparators.iter().all(| value | value.matches(&version)),
+            None => false,
+        };
+    }
+}
+
+fn parse_comparator(raw: &str) -> Result<Comparator, InvalidConstraintError> {
+    let operators = [
+        (">=", Operator::GreaterOrEqual),
+        ("<=", Operator::LessOrEqual),
+        (">", Operator::Greater),
+        ("<", Operator::Less),
+        ("=", Operator::Equal),
+    ];
+
+    let (operator, version) = operators.iter()
+        .find(| (prefix, _) | raw.starts_with(prefix))
+        .map(| (prefix, operator) | (*operator, &raw[prefix.len()..]))
+        .unwrap_or((Operator::Prefix, raw));
+
+    return match JavaVersion::parse(version) {
+        Some(version) => Ok(Comparator { operator, version }),
+        None => Err(InvalidConstraintError { part: raw.to_string() }),
+    };
+}
+
+// Finds JDK by exact name first, then by best one matching selector as version constraint.
+// Highest version wins, then vendor listed earlier in preferred_vendors, then name for determinism
+pub fn find_jdk_info<'a>(config: &'a Config, selector: &str) -> Option<&'a JdkInfo> {
+    return match_jdk_info(config, selector, true);
+}
+
+// Same as find_jdk_info but never traced, for resolving ahead of time like resolution cache
+pub(crate) fn find_jdk_info_quietly<'a>(config: &'a Config, selector: &str) -> Option<&'a JdkInfo> {
+    return match_jdk_info(config, selector, false);
+}
+
+fn match_jdk_info<'a>(config: &'a Config, selector: &str, traced: bool) -> Option<&'a JdkInfo> {
+    if let Some(jdk_info) = config.jdk_info(selector) {
+        if traced {
+            trace!("'{}' matches JDK name", selector);
+        }
+        return Some(jdk_info);
+    }
+
+    let constraint = match VersionConstraint::parse(selector) {
+        Ok(value) => value,
+        Err(e) => {
+            if traced {
+                trace!("'{}' isn't JDK name and {}", selector, e);
+            }
+            return None;
+        },
+    };
+
+    let candidates: Vec<&JdkInfo> = config.jdk_info_list.iter()
+        .filter(| value | constraint.matches(value))
+        .collect();
+    if traced {
+        let names: Vec<&str> = candidates.iter().map(| value | value.name.as_str()).collect();
+        trace!("'{}' as version constraint matches [{}]", selector, names.join(", "));
+    }
+
+    return newest_jdk_info(config, candidates.into_iter());
+}
+
+// Picks best candidate by same order used for version constraints
+pub fn newest_jdk_info<'a, I>(config: &Config, candidates: I) -> Option<&'a JdkInfo>
+where
+    I: Iterator<Item = &'a JdkInfo>,
+{
+    return candidates.max_by(| a, b | compare_candidates(config, a, b));
+}
+
+fn compare_candidates(config: &Config, a: &JdkInfo, b: &JdkInfo) -> Ordering {
+    let version_a = a.version.as_deref().and_then(JavaVersion::parse);
+    let version_b = b.version.as_deref().and_then(JavaVersion::parse);
+
+    return version_a.cmp(&version_b)
+        // Lower index in preferred list is better, unlisted vendors come last
+        .then_with(|| vendor_rank(config, b).cmp(&vendor_rank(config, a)))
+        .then_with(|| b.name.cmp(&a.name));
+}
+
+fn vendor_rank(config: &Config, jdk_info: &JdkInfo) -> usize {
+    let alias = match &jdk_info.vendor {
+        Some(value) => vendor_alias(value),
+        None => return usize::MAX,
+    };
+
+    return config.preferred_vendors.iter()
+        .position(| value | value.to_lowercase() == alias)
+        .unwrap_or(usize::MAX);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jdk(version: &str) -> JdkInfo {
+        let mut jdk_info = JdkInfo::new(version, "/opt/jdk");
+        jdk_info.version = Some(version.to_string());
+
+        return jdk_info;
+    }
+
+    fn matches(constraint: &str, version: &str) -> bool {
+        return VersionConstraint::parse(constraint).unwrap().matches(&jdk(version));
+    }
+
+    #[test]
+    fn bare_version_matches_by_prefix() {
+        assert!(matches("17", "17.0.8+7"));
+        assert!(matches("17", "17"));
+        assert!(!matches("17", "11.0.2"));
+        assert!(!matches("17", "1.8.0_321"));
+        assert!(matches("8", "1.8.0_321"));
+        assert!(!matches("21-ea", "21.0.1"));
+    }
+
+    #[test]
+    fn range_matches_partial_versions() {
+        assert!(matches(">=17", "17.0.8+7"));
+        assert!(matches(">=17", "21"));
+        assert!(!matches(">=17", "11.0.2"));
+        assert!(matches(">=11,<17", "11.0.2"));
+        assert!(!matches(">=11,<17", "17.0.8"));
+    }
+
+    #[test]
+    fn full_version_matches_exactly() {
+        assert!(matches("17.0.8", "17.0.8+7"));
+        assert!(!matches("17.0.8", "17.0.9"));
+        assert!(matches("=17.0.8", "17.0.8"));
+        assert!(!matches("=17.0.8", "17.0"));
+    }
+
+    #[test]
+    fn invalid_constraint_is_rejected() {
+        assert!(VersionConstraint::parse("").is_err());
+        assert!(VersionConstraint::parse(">=17.x").is_err());
+        assert!(VersionConstraint::parse("@17").is_err());
+    }
+}